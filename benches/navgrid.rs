@@ -0,0 +1,36 @@
+use broutus::game::map::{MapPosition, NavGrid, Occupancy};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// A map far larger than the arenas, with a regular pattern of obstacles
+/// so the search has to walk around them
+fn nav_grid(size: u32) -> NavGrid {
+    let mut nav_grid = NavGrid::new(size, size);
+    for y in (0..size).step_by(4) {
+        for x in (0..size).filter(|x| x % 7 != 0) {
+            nav_grid.set_blocked(&MapPosition::new(x, y), true);
+        }
+    }
+    nav_grid
+}
+
+fn reachable_positions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reachable_positions");
+    let occupancy = Occupancy::default();
+
+    for size in [100, 250, 500] {
+        let nav_grid = nav_grid(size);
+        let start = MapPosition::new(size / 2, size / 2 + 1);
+
+        for budget in [6, 30, size] {
+            let id = BenchmarkId::new(format!("{}x{}", size, size), budget);
+            group.bench_with_input(id, &budget, |b, &budget| {
+                b.iter(|| nav_grid.reachable_positions(&start, budget, &occupancy))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, reachable_positions);
+criterion_main!(benches);
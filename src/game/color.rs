@@ -31,6 +31,9 @@ pub const HIGHLIGHT_BORDER: Color = Color(195, 197, 213);
 pub const HEALTH: Color = Color(200, 18, 44);
 pub const ACTION_POINTS: Color = Color(247, 181, 56);
 pub const MOVEMENT_POINTS: Color = Color(74, 191, 178);
+pub const SHIELD: Color = Color(78, 65, 135);
+pub const EROSION: Color = Color(64, 14, 24);
+pub const OUT_OF_SIGHT: Color = Color(110, 108, 128);
 pub const TEAM_A_COLOR: Color = Color(91, 195, 235);
 pub const TEAM_B_COLOR: Color = Color(91, 209, 125);
 pub const TEAM_SPEC_COLOR: Color = Color(255, 255, 255);
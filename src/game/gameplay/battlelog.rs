@@ -0,0 +1,156 @@
+use super::{MapPosition, Turn, TurnEnd, TurnStart};
+use bevy::prelude::*;
+
+/// Something which happened during the fight, sent by the gameplay systems
+#[derive(Debug, Clone)]
+pub enum CombatEvent {
+    ActionUsed {
+        caster: Entity,
+        action: String,
+        to: MapPosition,
+    },
+    Damage {
+        caster: Entity,
+        target: Entity,
+        amount: u32,
+        /// Damages absorbed by the shield of the target
+        absorbed: u32,
+        is_crit: bool,
+    },
+    Heal {
+        caster: Entity,
+        target: Entity,
+        amount: u32,
+    },
+    /// Max health given back after an erosion
+    ErosionRestored {
+        caster: Entity,
+        target: Entity,
+        amount: u32,
+    },
+    ShieldGained {
+        caster: Entity,
+        target: Entity,
+        amount: u32,
+    },
+    ActionPointsLost {
+        caster: Entity,
+        target: Entity,
+        amount: u32,
+        /// The caster gained the lost action points
+        stolen: bool,
+    },
+    MovementPointsLost {
+        caster: Entity,
+        target: Entity,
+        amount: u32,
+        /// The caster gained the lost movement points
+        stolen: bool,
+    },
+    Push {
+        caster: Entity,
+        target: Entity,
+        to: MapPosition,
+    },
+    Teleport {
+        warrior: Entity,
+        to: MapPosition,
+    },
+    Movement {
+        warrior: Entity,
+        to: MapPosition,
+        cost: u32,
+    },
+    Death {
+        warrior: Entity,
+    },
+    TurnStart {
+        warrior: Entity,
+    },
+    TurnEnd {
+        warrior: Entity,
+    },
+}
+
+impl CombatEvent {
+    /// Check if the given warrior is the author or the target of the event
+    pub fn involves(&self, entity: Entity) -> bool {
+        match *self {
+            Self::ActionUsed { caster, .. } => caster == entity,
+            Self::Damage { caster, target, .. }
+            | Self::Heal { caster, target, .. }
+            | Self::ErosionRestored { caster, target, .. }
+            | Self::ShieldGained { caster, target, .. }
+            | Self::ActionPointsLost { caster, target, .. }
+            | Self::MovementPointsLost { caster, target, .. }
+            | Self::Push { caster, target, .. } => caster == entity || target == entity,
+            Self::Teleport { warrior, .. }
+            | Self::Movement { warrior, .. }
+            | Self::Death { warrior }
+            | Self::TurnStart { warrior }
+            | Self::TurnEnd { warrior } => warrior == entity,
+        }
+    }
+}
+
+pub struct BattleLogEntry {
+    /// The turn during which the event happened
+    pub turn: usize,
+    pub event: CombatEvent,
+}
+
+/// All the events of the current fight, the oldest first
+#[derive(Default)]
+pub struct BattleLog {
+    entries: Vec<BattleLogEntry>,
+
+    /// Only show the events involving this warrior, if any
+    pub filter: Option<Entity>,
+}
+
+impl BattleLog {
+    pub fn push(&mut self, turn: usize, event: CombatEvent) {
+        self.entries.push(BattleLogEntry { turn, event });
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.filter = None;
+    }
+
+    /// Iterate over the entries matching the current filter
+    pub fn iter(&self) -> impl Iterator<Item = &BattleLogEntry> {
+        let filter = self.filter;
+        self.entries.iter().filter(move |entry| match filter {
+            Some(entity) => entry.event.involves(entity),
+            None => true,
+        })
+    }
+}
+
+/// Start the battle log of a new fight
+pub fn clear_battlelog(mut battlelog: ResMut<BattleLog>) {
+    battlelog.clear();
+}
+
+/// Write the combat events and the turn changes in the battle log
+pub fn update_battlelog(
+    turn: Res<Turn>,
+    mut battlelog: ResMut<BattleLog>,
+    mut ev_combat: EventReader<CombatEvent>,
+    mut ev_turn_started: EventReader<TurnStart>,
+    mut ev_turn_ended: EventReader<TurnEnd>,
+) {
+    // A turn ends before the next one starts
+    for ev in ev_turn_ended.iter() {
+        battlelog.push(turn.current, CombatEvent::TurnEnd { warrior: ev.0 });
+    }
+
+    for ev in ev_turn_started.iter() {
+        battlelog.push(turn.current, CombatEvent::TurnStart { warrior: ev.0 });
+    }
+
+    for ev in ev_combat.iter() {
+        battlelog.push(turn.current, ev.clone());
+    }
+}
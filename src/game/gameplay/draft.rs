@@ -0,0 +1,198 @@
+use super::{TeamRoster, TeamSide, WarriorAsset, WarriorCollection, ROSTER_SIZE};
+use bevy::prelude::*;
+
+/// Extra picks added at the end of the draft for the incomplete rosters,
+/// the pool may not have enough warriors left to complete them
+const MAX_EXTRA_PICKS: usize = 2 * ROSTER_SIZE;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DraftAction {
+    /// Forbid a warrior to both teams
+    Ban,
+
+    /// Add a warrior to the team roster
+    Pick,
+}
+
+/// A draft step, a team has to ban or pick a warrior
+#[derive(Copy, Clone, Debug)]
+pub struct DraftStep {
+    pub side: TeamSide,
+    pub action: DraftAction,
+}
+
+impl DraftStep {
+    pub const fn new(side: TeamSide, action: DraftAction) -> Self {
+        Self { side, action }
+    }
+}
+
+/// Alternating pick / ban draft, when this resource exists the Picking state is in draft mode
+pub struct Draft {
+    /// Steps of the draft, in order
+    sequence: Vec<DraftStep>,
+
+    /// Index of the current step in the sequence
+    step_index: usize,
+
+    /// Warriors banned so far, they cannot be picked by any team
+    bans: Vec<Handle<WarriorAsset>>,
+
+    /// Picks added to the sequence to complete the rosters
+    extra_picks: usize,
+}
+
+impl Default for Draft {
+    fn default() -> Self {
+        Self::new(vec![
+            DraftStep::new(TeamSide::A, DraftAction::Ban),
+            DraftStep::new(TeamSide::B, DraftAction::Ban),
+            DraftStep::new(TeamSide::A, DraftAction::Pick),
+            DraftStep::new(TeamSide::B, DraftAction::Pick),
+            DraftStep::new(TeamSide::B, DraftAction::Pick),
+            DraftStep::new(TeamSide::A, DraftAction::Pick),
+            DraftStep::new(TeamSide::A, DraftAction::Pick),
+            DraftStep::new(TeamSide::B, DraftAction::Pick),
+        ])
+    }
+}
+
+impl Draft {
+    /// Create a draft from its steps, completed with the picks needed to fill both rosters
+    pub fn new(mut sequence: Vec<DraftStep>) -> Self {
+        for side in [TeamSide::A, TeamSide::B] {
+            let picks = sequence
+                .iter()
+                .filter(|step| step.side == side && step.action == DraftAction::Pick)
+                .count();
+            for _ in picks..ROSTER_SIZE {
+                sequence.push(DraftStep::new(side, DraftAction::Pick));
+            }
+        }
+
+        Self {
+            sequence,
+            step_index: 0,
+            bans: Vec::new(),
+            extra_picks: 0,
+        }
+    }
+
+    /// Get the step to play, none if the draft is completed
+    pub fn current_step(&self) -> Option<DraftStep> {
+        self.sequence.get(self.step_index).copied()
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.current_step().is_none()
+    }
+
+    pub fn is_banned(&self, warrior: &Handle<WarriorAsset>) -> bool {
+        self.bans.contains(warrior)
+    }
+
+    /// Can the warrior be banned or picked at the current step
+    pub fn can_choose(&self, roster: &TeamRoster, warrior: &Handle<WarriorAsset>) -> bool {
+        if self.is_banned(warrior) {
+            return false;
+        }
+
+        match self.current_step() {
+            Some(DraftStep {
+                action: DraftAction::Ban,
+                ..
+            }) => roster.picked_by(warrior).is_empty(),
+            Some(DraftStep {
+                side,
+                action: DraftAction::Pick,
+            }) => roster.can_pick(side, warrior),
+            None => false,
+        }
+    }
+
+    /// Ban or pick the warrior for the current step, moving to the next step on success
+    pub fn choose(&mut self, roster: &mut TeamRoster, warrior: &Handle<WarriorAsset>) -> bool {
+        if !self.can_choose(roster, warrior) {
+            return false;
+        }
+
+        if let Some(step) = self.current_step() {
+            match step.action {
+                DraftAction::Ban => self.bans.push(warrior.clone()),
+                DraftAction::Pick => {
+                    roster.pick(step.side, warrior);
+                }
+            }
+        }
+
+        self.next_step(roster);
+        true
+    }
+
+    /// Move to the next step without choosing any warrior
+    pub fn skip(&mut self, roster: &mut TeamRoster) {
+        self.next_step(roster);
+    }
+
+    /// Move forward in the sequence, confirming both rosters once the draft is completed.
+    /// The draft stays open with a limited number of extra picks while a roster is incomplete
+    fn next_step(&mut self, roster: &mut TeamRoster) {
+        self.step_index += 1;
+
+        if self.is_completed() {
+            for side in [TeamSide::A, TeamSide::B] {
+                if !roster.is_complete(side) && self.extra_picks < MAX_EXTRA_PICKS {
+                    self.sequence.push(DraftStep::new(side, DraftAction::Pick));
+                    self.extra_picks += 1;
+                }
+            }
+        }
+
+        if self.is_completed() {
+            roster.confirm(TeamSide::A);
+            roster.confirm(TeamSide::B);
+        }
+    }
+}
+
+pub struct DraftTimer(pub Timer);
+
+impl Default for DraftTimer {
+    fn default() -> Self {
+        DraftTimer(Timer::from_seconds(30.0, true))
+    }
+}
+
+/// Play the current draft step when its time is over: skip the ban or pick the first available warrior
+pub fn run_draft_timer(
+    time: Res<Time>,
+    draft: Option<ResMut<Draft>>,
+    mut timer: ResMut<DraftTimer>,
+    mut roster: ResMut<TeamRoster>,
+    warrior_collection: Res<WarriorCollection>,
+) {
+    let mut draft = match draft {
+        Some(draft) => draft,
+        None => return,
+    };
+
+    if draft.is_completed() {
+        return;
+    }
+
+    if timer.0.tick(time.delta()).just_finished() {
+        let step = draft.current_step().unwrap();
+        let available = warrior_collection
+            .warriors
+            .iter()
+            .find(|warrior| draft.can_choose(&roster, warrior))
+            .cloned();
+
+        match (step.action, available) {
+            (DraftAction::Pick, Some(warrior)) => {
+                draft.choose(&mut roster, &warrior);
+            }
+            _ => draft.skip(&mut roster),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use super::{color, CombatEvent, Map, MapPosition};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Seconds before a floating text disappears
+const FLOATING_TEXT_DURATION: f32 = 1.2;
+/// Distance travelled upward by a floating text, in world units
+const FLOATING_TEXT_RISE: f32 = 40.0;
+/// Vertical gap between the texts spawned at once above the same warrior
+const FLOATING_TEXT_GAP: f32 = 18.0;
+/// Height of the text above the position of the warrior
+const FLOATING_TEXT_OFFSET: f32 = 60.0;
+
+/// A number rising above a warrior after a combat event, fading over time
+#[derive(Component)]
+pub struct FloatingText {
+    pub text: String,
+    pub color: color::Color,
+    pub is_crit: bool,
+    elapsed: f32,
+}
+
+impl FloatingText {
+    pub fn new(text: String, color: color::Color, is_crit: bool) -> Self {
+        Self {
+            text,
+            color,
+            is_crit,
+            elapsed: 0.0,
+        }
+    }
+
+    /// From 1.0 when spawned to 0.0 when it disappears
+    pub fn opacity(&self) -> f32 {
+        (1.0 - self.elapsed / FLOATING_TEXT_DURATION).clamp(0.0, 1.0)
+    }
+}
+
+/// Spawn the floating texts above the targets of the combat events
+pub fn spawn_floating_texts(
+    mut commands: Commands,
+    mut ev_combat: EventReader<CombatEvent>,
+    map_query: Query<&Map>,
+    position_query: Query<&MapPosition>,
+) {
+    if map_query.is_empty() {
+        return;
+    }
+    let map = map_query.single();
+
+    // Stack the texts spawned on the same warrior during this frame
+    let mut stacks: HashMap<Entity, usize> = HashMap::new();
+
+    for ev in ev_combat.iter() {
+        let (target, texts) = match *ev {
+            CombatEvent::Damage {
+                target,
+                amount,
+                absorbed,
+                is_crit,
+                ..
+            } => {
+                let mut texts = vec![];
+                if amount > 0 {
+                    texts.push(FloatingText::new(
+                        format!("-{}", amount),
+                        color::HEALTH,
+                        is_crit,
+                    ));
+                }
+                if absorbed > 0 {
+                    texts.push(FloatingText::new(
+                        format!("-{}", absorbed),
+                        color::SHIELD,
+                        is_crit,
+                    ));
+                }
+                (target, texts)
+            }
+            CombatEvent::Heal { target, amount, .. } => (
+                target,
+                vec![FloatingText::new(
+                    format!("+{}", amount),
+                    color::HEALTH,
+                    false,
+                )],
+            ),
+            CombatEvent::ShieldGained { target, amount, .. } => (
+                target,
+                vec![FloatingText::new(
+                    format!("+{}", amount),
+                    color::SHIELD,
+                    false,
+                )],
+            ),
+            CombatEvent::ActionPointsLost { target, amount, .. } => (
+                target,
+                vec![FloatingText::new(
+                    format!("-{} AP", amount),
+                    color::ACTION_POINTS,
+                    false,
+                )],
+            ),
+            CombatEvent::MovementPointsLost { target, amount, .. } => (
+                target,
+                vec![FloatingText::new(
+                    format!("-{} MP", amount),
+                    color::MOVEMENT_POINTS,
+                    false,
+                )],
+            ),
+            _ => continue,
+        };
+
+        let position = match position_query.get(target) {
+            Ok(position) => position,
+            Err(_) => continue,
+        };
+        let world_position = position.to_xyz(
+            0u32,
+            map.width,
+            map.height,
+            map.tile_width as f32,
+            map.tile_height as f32,
+        );
+
+        for text in texts {
+            let stack = stacks.entry(target).or_insert(0);
+            let translation = world_position
+                + Vec3::new(
+                    0.0,
+                    FLOATING_TEXT_OFFSET + *stack as f32 * FLOATING_TEXT_GAP,
+                    0.0,
+                );
+            *stack += 1;
+
+            commands
+                .spawn()
+                .insert(Name::new("Floating text"))
+                .insert(text)
+                .insert(Transform::from_translation(translation))
+                .insert(GlobalTransform::default());
+        }
+    }
+}
+
+/// Make the floating texts rise, then despawn them once faded
+pub fn animate_floating_texts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut FloatingText, &mut Transform)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut text, mut transform) in query.iter_mut() {
+        text.elapsed += delta;
+        transform.translation.y += FLOATING_TEXT_RISE * delta / FLOATING_TEXT_DURATION;
+
+        if text.elapsed >= FLOATING_TEXT_DURATION {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Remove the floating texts left when the fight ends
+pub fn despawn_floating_texts(mut commands: Commands, query: Query<Entity, With<FloatingText>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -2,73 +2,217 @@ use super::color;
 use super::GameState;
 use bevy::prelude::*;
 
-mod attribute;
+mod battlelog;
+mod draft;
+mod floating_text;
+mod placement;
+mod random;
+mod results;
+mod roster;
+mod status;
 mod team;
+mod timeline;
 mod turn;
-mod warrior;
 mod warrior_new;
-mod weapon;
 
+pub use warrior_new::animate_warrior_movement;
+pub use warrior_new::animate_warrior_sprite;
+pub use warrior_new::face_action_target;
+pub use warrior_new::inflict_damage;
+pub use warrior_new::play_combat_animations;
+pub use warrior_new::update_warrior_world_position;
+pub use warrior_new::Action;
+pub use warrior_new::ActionEffect;
+pub use warrior_new::ActionPoints;
+pub use warrior_new::ActionTargetQuery;
+pub use warrior_new::Actions;
 pub use warrior_new::AnimationCollection;
+pub use warrior_new::AnimationController;
+pub use warrior_new::AnimationFinished;
+pub use warrior_new::Attribute;
+pub use warrior_new::Dead;
+pub use warrior_new::Erosion;
+pub use warrior_new::Health;
 pub use warrior_new::IconCollection;
+pub use warrior_new::Initiative;
+pub use warrior_new::MovementPoints;
 pub use warrior_new::PortraitCollection;
+pub use warrior_new::SelectedAction;
+pub use warrior_new::Shield;
+pub use warrior_new::Status;
+pub use warrior_new::StatusEffects;
+pub use warrior_new::StatusKind;
+pub use warrior_new::Warrior;
 pub use warrior_new::WarriorAsset;
 pub use warrior_new::WarriorAssetLoader;
+pub use warrior_new::WarriorBundle;
 pub use warrior_new::WarriorCollection;
+pub use warrior_new::WarriorMovement;
 
 pub use super::map::Map;
 pub use super::map::MapPosition;
 pub use super::map::MapPositionDirection;
 pub use super::map::MapQuery;
+pub use super::map::MapsAssets;
 pub use super::map::MouseMapPosition;
+pub use super::map::NavGrid;
+pub use super::map::Occupancy;
 pub use super::map::Tile;
 pub use super::map::TileLeftClickedEvent;
 pub use super::map::TileRightClickedEvent;
-pub use attribute::ActionPoints;
-pub use attribute::Attribute;
-pub use attribute::Health;
-pub use attribute::MovementPoints;
+pub use super::map::Tiledmap;
+pub use super::map::SPAWN_TEAM_A_LAYER;
+pub use super::map::SPAWN_TEAM_B_LAYER;
+pub use battlelog::clear_battlelog;
+pub use battlelog::update_battlelog;
+pub use battlelog::BattleLog;
+pub use battlelog::BattleLogEntry;
+pub use battlelog::CombatEvent;
+pub use draft::run_draft_timer;
+pub use draft::Draft;
+pub use draft::DraftAction;
+pub use draft::DraftStep;
+pub use draft::DraftTimer;
+pub use floating_text::animate_floating_texts;
+pub use floating_text::despawn_floating_texts;
+pub use floating_text::spawn_floating_texts;
+pub use floating_text::FloatingText;
+pub use placement::drag_and_drop_warriors;
+pub use placement::highlight_spawn_positions;
+pub use placement::place_warriors_on_spawn;
+pub use placement::Placement;
+pub use random::seed_match_rng;
+pub use random::GameRng;
+pub use results::despawn_warriors;
+pub use results::detect_match_end;
+pub use results::record_match_stats;
+pub use results::start_match_stats;
+pub use results::MatchResult;
+pub use results::MatchStats;
+pub use results::WarriorStats;
+pub use roster::TeamRoster;
+pub use roster::ROSTER_SIZE;
+pub use status::apply_status_effects;
+pub use status::detect_stunned_warrior_turn;
+pub use status::expire_status_effects;
+pub use status::skip_stunned_warrior_turn;
 pub use team::Team;
 pub use team::TeamSide;
+pub use timeline::resolve_pending_actions;
+pub use timeline::BusyWarriorQuery;
+pub use timeline::FastMode;
+pub use timeline::PendingAction;
+pub use turn::build_turn_order;
 pub use turn::reset_turn_timer;
 pub use turn::run_turn_timer;
+pub use turn::start_turns;
+pub use turn::update_turn_order;
 pub use turn::Turn;
 pub use turn::TurnEnd;
+pub use turn::TurnSkip;
 pub use turn::TurnStart;
 pub use turn::TurnTimer;
-pub use warrior::animate_warrior_sprite;
-pub use warrior::update_warrior_world_position;
-pub use warrior::Warrior;
-pub use warrior::WarriorAssets;
-pub use warrior::WarriorBundle;
-pub use weapon::SelectedAction;
-pub use weapon::Weapon;
-pub use weapon::HEAL_WAND;
-pub use weapon::THUG_KNIFE;
+
+/// Allies can be crossed by a moving warrior, while enemies block the way
+const ALLIES_ARE_PASSABLE: bool = true;
+
+/// Extra movement points to leave a cell next to an enemy
+const ZONE_OF_CONTROL_COST: u32 = 1;
 
 pub struct GameplayPlugin;
 
 impl Plugin for GameplayPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<Attribute>()
-            .register_type::<Health>()
-            .register_type::<ActionPoints>()
-            .register_type::<MovementPoints>()
+        app.register_type::<Attribute<Health>>()
+            .register_type::<Attribute<Shield>>()
+            .register_type::<Attribute<ActionPoints>>()
+            .register_type::<Attribute<MovementPoints>>()
+            .register_type::<Attribute<Initiative>>()
+            .register_type::<AnimationController>()
             .init_resource::<SelectedAction>()
+            .init_resource::<TeamRoster>()
+            .init_resource::<DraftTimer>()
+            .init_resource::<Placement>()
+            .init_resource::<MatchStats>()
+            .init_resource::<BattleLog>()
+            .init_resource::<GameRng>()
+            .init_resource::<FastMode>()
             .add_asset::<WarriorAsset>()
             .add_asset_loader(WarriorAssetLoader)
             .add_event::<TurnStart>()
             .add_event::<TurnEnd>()
-            .add_system_set(SystemSet::on_enter(GameState::Arena).with_system(spawn_warriors))
+            .add_event::<TurnSkip>()
+            .add_event::<CombatEvent>()
+            .add_event::<AnimationFinished>()
+            .add_system_set(SystemSet::on_update(GameState::Picking).with_system(run_draft_timer))
+            .add_system_set(SystemSet::on_enter(GameState::Placement).with_system(spawn_warriors))
+            .add_system_set(
+                SystemSet::on_update(GameState::Placement)
+                    .with_system(place_warriors_on_spawn)
+                    .with_system(drag_and_drop_warriors)
+                    .with_system(update_nav_grid_occupants)
+                    .with_system(animate_warrior_sprite)
+                    .with_system(update_warrior_world_position),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Placement)
+                    .label("clean_placement_highlighting")
+                    .with_system(unhighlight_all_tiles),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Placement)
+                    .after("clean_placement_highlighting")
+                    .with_system(highlight_spawn_positions),
+            )
+            .add_system_set(
+                SystemSet::on_enter(GameState::Arena)
+                    .with_system(start_turns)
+                    .with_system(start_match_stats)
+                    .with_system(clear_battlelog)
+                    .with_system(seed_match_rng),
+            )
             .add_system_set(
                 SystemSet::on_update(GameState::Arena)
+                    .with_system(update_turn_order)
                     .with_system(run_turn_timer)
                     .with_system(reset_turn_timer)
                     .with_system(animate_warrior_sprite)
-                    .with_system(update_warrior_world_position)
+                    .with_system(animate_warrior_movement)
+                    .with_system(update_warrior_world_position.before("warrior_action"))
                     .with_system(reset_warrior_attributes_on_turn_end)
-                    .with_system(handle_warrior_action_on_click)
-                    .with_system(despawn_warrior_on_death),
+                    .with_system(
+                        apply_status_effects
+                            .label("status_effects")
+                            .before("warrior_death"),
+                    )
+                    .with_system(expire_status_effects)
+                    .with_system(
+                        detect_stunned_warrior_turn
+                            .label("warrior_stun")
+                            .after("status_effects"),
+                    )
+                    .with_system(skip_stunned_warrior_turn.after("warrior_stun"))
+                    .with_system(update_nav_grid_occupants.before("warrior_action"))
+                    .with_system(handle_warrior_action_on_click.label("warrior_action"))
+                    .with_system(resolve_pending_actions.label("warrior_action"))
+                    .with_system(
+                        despawn_warrior_on_death
+                            .label("warrior_death")
+                            .after("warrior_action"),
+                    )
+                    // Read the combat events in the same frame, the match may end right after
+                    .with_system(record_match_stats.after("warrior_death"))
+                    .with_system(update_battlelog.after("warrior_death"))
+                    .with_system(face_action_target.after("warrior_action"))
+                    .with_system(
+                        play_combat_animations
+                            .after("warrior_action")
+                            .before("warrior_death"),
+                    )
+                    .with_system(despawn_dead_warriors)
+                    .with_system(spawn_floating_texts.after("warrior_action"))
+                    .with_system(animate_floating_texts)
+                    .with_system(detect_match_end.after("warrior_death")),
             )
             .add_system_set(
                 SystemSet::on_update(GameState::Arena)
@@ -88,87 +232,68 @@ impl Plugin for GameplayPlugin {
                     .label("highlight_2")
                     .after("highlight_1")
                     .with_system(highlight_potential_action),
-            );
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Arena).with_system(despawn_floating_texts),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Results).with_system(despawn_warriors));
     }
 }
 
-fn spawn_warriors(mut commands: Commands, warrior_assets: Res<WarriorAssets>) {
-    let team_a = Team::new(TeamSide::A, color::TEAM_A_COLOR);
-    let team_b = Team::new(TeamSide::B, color::TEAM_B_COLOR);
-
-    // Spawn warriors
-    let brundal = commands
-        .spawn_bundle(WarriorBundle::new(
-            "Brundal".to_string(),
-            MapPosition::new(17, 5),
-            &THUG_KNIFE,
-            -1.0,
-            &warrior_assets.idle,
-        ))
-        .insert(team_a.clone())
-        .id();
-
-    let brandy = commands
-        .spawn_bundle(WarriorBundle::new(
-            "Brandy".to_string(),
-            MapPosition::new(17, 10),
-            &HEAL_WAND,
-            -1.0,
-            &warrior_assets.idle,
-        ))
-        .insert(team_a.clone())
-        .id();
-
-    let brando = commands
-        .spawn_bundle(WarriorBundle::new(
-            "Brando".to_string(),
-            MapPosition::new(17, 2),
-            &THUG_KNIFE,
-            -1.0,
-            &warrior_assets.idle,
-        ))
-        .insert(team_a.clone())
-        .id();
-
-    let glourf = commands
-        .spawn_bundle(WarriorBundle::new(
-            "Glourf".to_string(),
-            MapPosition::new(2, 5),
-            &THUG_KNIFE,
-            1.0,
-            &warrior_assets.idle,
-        ))
-        .insert(team_b.clone())
-        .id();
-
-    let glarf = commands
-        .spawn_bundle(WarriorBundle::new(
-            "Glarf".to_string(),
-            MapPosition::new(2, 1),
-            &HEAL_WAND,
-            1.0,
-            &warrior_assets.idle,
-        ))
-        .insert(team_b.clone())
-        .id();
-
-    let glirf = commands
-        .spawn_bundle(WarriorBundle::new(
-            "Glirf".to_string(),
-            MapPosition::new(2, 8),
-            &THUG_KNIFE,
-            1.0,
-            &warrior_assets.idle,
-        ))
-        .insert(team_b.clone())
-        .id();
-
-    // Insert turn system resource
+/// Spawn the warriors picked by each team on the spawn cells of their team
+fn spawn_warriors(
+    mut commands: Commands,
+    roster: Res<TeamRoster>,
+    warriors: Res<Assets<WarriorAsset>>,
+    animation_collection: Res<AnimationCollection>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    maps_assets: Res<MapsAssets>,
+    tiledmaps: Res<Assets<Tiledmap>>,
+) {
+    let tiledmap = tiledmaps.get(maps_assets.simple.clone());
+
+    for side in [TeamSide::A, TeamSide::B] {
+        // The teams face each other
+        let (facing, spawn_layer) = match side {
+            TeamSide::A => (MapPositionDirection::SudEst, SPAWN_TEAM_A_LAYER),
+            TeamSide::B => (MapPositionDirection::NordWest, SPAWN_TEAM_B_LAYER),
+        };
+        let spawn_positions = tiledmap
+            .map(|tiledmap| tiledmap.layer_positions(spawn_layer))
+            .unwrap_or_default();
+
+        for (index, handle) in roster.get(side).iter().enumerate() {
+            let asset = match warriors.get(handle) {
+                Some(asset) => asset,
+                None => continue,
+            };
+
+            let texture_atlas = animation_collection
+                .get(&asset.render.atlas_texture)
+                .unwrap_or_else(|| panic!("Missing {} atlas", asset.render.atlas_texture));
+            let atlas_len = texture_atlases
+                .get(&texture_atlas)
+                .map_or(0, |texture_atlas| texture_atlas.textures.len());
+
+            // Warriors left without a spawn cell are placed during the placement phase
+            let position = spawn_positions.get(index).copied().unwrap_or_default();
+
+            commands
+                .spawn_bundle(WarriorBundle::new(
+                    asset.name.clone(),
+                    position,
+                    asset,
+                    facing,
+                    &texture_atlas,
+                    atlas_len,
+                ))
+                .insert(Team::new(side, side.color()));
+        }
+    }
+
+    // Insert turn system resource, the turn order is built once the fight starts
     commands.insert_resource(TurnTimer::default());
-    commands.insert_resource(Turn {
-        order: vec![brundal, glourf, brandy, glarf, brando, glirf],
-        ..Default::default()
-    })
+    commands.insert_resource(Turn::default());
 }
 
 /// Clean tile highlighting with white color
@@ -211,7 +336,8 @@ fn compute_and_highlight_path(
     turn: Res<Turn>,
     selected_action: Res<SelectedAction>,
     mouse_position: Res<MouseMapPosition>,
-    warrior_query: Query<(&MapPosition, &MovementPoints), With<Warrior>>,
+    warrior_query: Query<(Entity, &MapPosition, &Attribute<MovementPoints>), With<Warrior>>,
+    team_query: Query<&Team, With<Warrior>>,
     mut map_query: MapQuery,
 ) {
     // An action is selected, don't highlight path
@@ -223,40 +349,37 @@ fn compute_and_highlight_path(
     let (_, map, _) = map_query.map_queryset.q1().single();
     let map_id = map.id;
     let highlight_layer_id = map.highlight_layer;
-    let map_width = map.width;
-    let map_height = map.height;
 
     let warrior_position = turn
         .get_current_warrior_entity()
         .and_then(|e| warrior_query.get(e).ok());
 
-    if let Some((warrior_position, movement_points)) = warrior_position {
+    if let Some((warrior_entity, warrior_position, movement_points)) = warrior_position {
         if mouse_position.is_changed() {
             if let Some(mouse_position) = mouse_position.0 {
-                let path = map_query.pathfinding(
-                    map_id,
-                    warrior_position,
-                    &mouse_position,
-                    map_width,
-                    map_height,
+                let occupancy = get_warrior_occupancy(
+                    warrior_entity,
+                    warrior_query
+                        .iter()
+                        .map(|(entity, position, _)| (entity, *position)),
+                    &team_query,
                 );
+                let path = map_query.pathfinding(warrior_position, &mouse_position, &occupancy);
 
                 if let Some((path, cost)) = path {
-                    if cost <= movement_points.0.value {
-                        for position in path
-                            .iter()
-                            .skip(1)
-                            .take(movement_points.0.value as usize + 1)
-                        {
-                            map_query.update_tile_sprite_color(
-                                map_id,
-                                highlight_layer_id,
-                                position,
-                                bevy::render::color::Color::from(color::MOVEMENT_POINTS)
-                                    .set_a(0.8)
-                                    .as_rgba(),
-                            );
-                        }
+                    if cost > movement_points.value() {
+                        return;
+                    }
+
+                    for position in path.iter().skip(1) {
+                        map_query.update_tile_sprite_color(
+                            map_id,
+                            highlight_layer_id,
+                            position,
+                            bevy::render::color::Color::from(color::MOVEMENT_POINTS)
+                                .set_a(0.8)
+                                .as_rgba(),
+                        );
                     }
                 }
             }
@@ -264,11 +387,12 @@ fn compute_and_highlight_path(
     }
 }
 
-/// A fucking inefficient function to highlight reachable MapPositions for the hovered Warrior
+/// Highlight the reachable MapPositions of the hovered Warrior
 fn highlight_potential_movement(
     mouse_position: Res<MouseMapPosition>,
     selected_action: Res<SelectedAction>,
-    warrior_query: Query<(&MapPosition, &MovementPoints), With<Warrior>>,
+    warrior_query: Query<(Entity, &MapPosition, &Attribute<MovementPoints>), With<Warrior>>,
+    team_query: Query<&Team, With<Warrior>>,
     mut map_query: MapQuery,
 ) {
     // An action is selected, don't highlight path
@@ -279,43 +403,34 @@ fn highlight_potential_movement(
     let (_, map, _) = map_query.map_queryset.q1().single();
     let map_id = map.id;
     let highlight_layer_id = map.highlight_layer;
-    let map_width = map.width;
-    let map_height = map.height;
 
-    for (warrior_position, movement_points) in warrior_query.iter() {
+    for (warrior_entity, warrior_position, movement_points) in warrior_query.iter() {
         if mouse_position.is_changed() {
             if let Some(mouse_position) = mouse_position.0 {
                 // The mouse is over a warrior, let's highlight it's potential movement
                 if mouse_position.eq(warrior_position) {
-                    let surroundings = warrior_position.get_surrounding_positions(
-                        1,
-                        movement_points.0.value,
-                        map_width,
-                        map_height,
+                    let occupancy = get_warrior_occupancy(
+                        warrior_entity,
+                        warrior_query
+                            .iter()
+                            .map(|(entity, position, _)| (entity, *position)),
+                        &team_query,
+                    );
+                    let reachable = map_query.reachable_positions(
+                        warrior_position,
+                        movement_points.value(),
+                        &occupancy,
                     );
 
-                    for position in surroundings {
-                        // Yes that is horrible
-                        let path = map_query.pathfinding(
+                    for (position, _) in reachable.iter() {
+                        map_query.update_tile_sprite_color(
                             map_id,
-                            warrior_position,
-                            &position,
-                            map_width,
-                            map_height,
+                            highlight_layer_id,
+                            position,
+                            bevy::render::color::Color::from(color::MOVEMENT_POINTS)
+                                .set_a(0.6)
+                                .as_rgba(),
                         );
-
-                        if let Some((_, cost)) = path {
-                            if cost <= movement_points.0.value {
-                                map_query.update_tile_sprite_color(
-                                    map_id,
-                                    highlight_layer_id,
-                                    &position,
-                                    bevy::render::color::Color::from(color::MOVEMENT_POINTS)
-                                        .set_a(0.6)
-                                        .as_rgba(),
-                                );
-                            }
-                        }
                     }
                 }
             }
@@ -323,99 +438,69 @@ fn highlight_potential_movement(
     }
 }
 
-// /// Move the warrior on click if he can afford the cost of the path in movement points
+/// Execute the selected action on click, or move the warrior if he can afford the cost of the path in movement points
 fn handle_warrior_action_on_click(
+    mut commands: Commands,
     turn: Res<Turn>,
     mut ev_clicked: EventReader<TileLeftClickedEvent>,
     mut selected_action: ResMut<SelectedAction>,
-    mut warrior_query: Query<
-        (
-            &Weapon,
-            &mut MapPosition,
-            &mut ActionPoints,
-            &mut MovementPoints,
-            &mut Health,
-        ),
-        (With<Warrior>, Without<Tile>),
-    >,
-    mut map_query: MapQuery,
+    actions_query: Query<&Actions, With<Warrior>>,
+    team_query: Query<&Team, With<Warrior>>,
+    mut warrior_query: ActionTargetQuery,
+    nav_grid: Res<NavGrid>,
+    mut rng: ResMut<GameRng>,
+    mut ev_combat: EventWriter<CombatEvent>,
+    fast_mode: Res<FastMode>,
+    busy_query: BusyWarriorQuery,
 ) {
-    let (_, map, _) = map_query.map_queryset.q1().single();
-    let map_id = map.id;
-    let map_width = map.width;
-    let map_height = map.height;
+    let warrior_entity = match turn.get_current_warrior_entity() {
+        Some(entity) => entity,
+        None => return,
+    };
 
-    if let Some(index) = selected_action.0 {
-        let (_, cost, min_distance, max_distance) = match index {
-            0 => ("Slash", 3, 1, 2),
-            1 => ("Shoot", 5, 3, 5),
-            2 => ("Cripple", 3, 1, 2),
-            3 => ("Blind", 3, 1, 1),
-            4 => ("Push", 2, 1, 2),
-            5 => ("Teleport", 5, 2, 5),
-            6 => ("Shield", 3, 0, 0),
-            _ => ("Heal", 4, 0, 1),
-        };
+    // Ignore the clicks until the warriors are done walking and acting
+    if !busy_query.is_empty() {
+        ev_clicked.iter().for_each(drop);
+        return;
+    }
 
+    if let Some(index) = selected_action.0 {
         for click_event in ev_clicked.iter() {
-            let warrior_entity = turn.get_current_warrior_entity().unwrap();
-            let (_, mut position, mut action_points, _, _) =
-                warrior_query.get_mut(warrior_entity).unwrap();
-
-            let distance_to_event = position.distance_to(&click_event.0);
-
-            if distance_to_event < min_distance {
-                continue;
-            }
-
-            if distance_to_event > max_distance {
-                continue;
-            }
-
-            if !map_query.line_of_sight_check(
-                map_id,
-                &position,
-                &click_event.0,
-                map_width,
-                map_height,
-            ) {
-                continue;
-            }
-
-            let direction = position.direction_to(&click_event.0);
-
-            if action_points.can_spend(cost) {
-                action_points.spend(cost);
-
-                if index == 5 {
-                    position.x = click_event.0.x;
-                    position.y = click_event.0.y;
-                }
-
-                for (_, mut position, mut ap, mut mp, mut health) in warrior_query.iter_mut() {
-                    if click_event.0.eq(&position) {
-                        match index {
-                            0 => health.hurt(170),
-                            1 => health.hurt(90),
-                            2 => mp.spend(2),
-                            3 => ap.spend(2),
-                            4 => {
-                                if let Some(direction) = direction {
-                                    let path = position.unchecked_path_torward(direction, 2);
-                                    let mut path_iter = path.iter();
-                                    while let Some(pos) = path_iter.next() {
-                                        if map_query.is_obstacle(map_id, pos, map_width, map_height)
-                                        {
-                                            break;
-                                        }
-                                        position.x = pos.x;
-                                        position.y = pos.y;
-                                    }
-                                }
-                            }
-                            6 => health.heal(120),
-                            _ => health.heal(120),
-                        }
+            let action = actions_query
+                .get(warrior_entity)
+                .ok()
+                .and_then(|actions| actions.get(index));
+
+            if let Some(action) = action {
+                let (_, position, _, _, _, _, action_points, _) =
+                    warrior_query.get(warrior_entity).unwrap();
+                let from_position = *position;
+                let can_afford = action_points.can_drop(action.cost.0);
+
+                if can_afford && action.can_target(&from_position, &click_event.0, &nav_grid) {
+                    let (_, _, _, _, _, _, mut action_points, _) =
+                        warrior_query.get_mut(warrior_entity).unwrap();
+                    action_points.drop(action.cost.0);
+
+                    ev_combat.send(CombatEvent::ActionUsed {
+                        caster: warrior_entity,
+                        action: action.name.clone(),
+                        to: click_event.0,
+                    });
+
+                    if fast_mode.0 {
+                        action.execute(
+                            warrior_entity,
+                            &click_event.0,
+                            &nav_grid,
+                            &mut warrior_query,
+                            &mut rng,
+                            &mut ev_combat,
+                        );
+                    } else {
+                        commands
+                            .entity(warrior_entity)
+                            .insert(PendingAction::new(action.clone(), click_event.0));
                     }
                 }
             }
@@ -424,21 +509,41 @@ fn handle_warrior_action_on_click(
         }
     } else {
         for ev in ev_clicked.iter() {
-            let warrior_entity = turn.get_current_warrior_entity().unwrap();
-            if let Ok((_, mut warrior_position, _, mut movement_points, _)) =
+            // The occupied destinations are never reachable
+            let occupancy = get_warrior_occupancy(
+                warrior_entity,
+                warrior_query
+                    .iter()
+                    .map(|(entity, position, ..)| (entity, *position)),
+                &team_query,
+            );
+
+            if let Ok((_, mut warrior_position, .., mut movement_points)) =
                 warrior_query.get_mut(warrior_entity)
             {
-                let path =
-                    map_query.pathfinding(map_id, &warrior_position, &ev.0, map_width, map_height);
-
-                // TODO Replace the current sprite sheets by another one containing all 4 directions
-                // TODO Animate warrior movement along the path
-                // TODO Change warrior orientation when it changes direction
-                if let Some((_path, cost)) = path {
-                    if movement_points.can_spend(cost) {
+                let reachable = nav_grid.reachable_positions(
+                    &warrior_position,
+                    movement_points.value(),
+                    &occupancy,
+                );
+
+                let path = reachable.path_to(&ev.0);
+                if let (Some(cost), Some(path)) = (reachable.cost(&ev.0), path) {
+                    if movement_points.can_drop(cost) {
+                        // The warrior is on its destination right away, its sprite walks there
                         warrior_position.x = ev.0.x;
                         warrior_position.y = ev.0.y;
-                        movement_points.spend(cost);
+                        movement_points.drop(cost);
+                        if !fast_mode.0 {
+                            commands
+                                .entity(warrior_entity)
+                                .insert(WarriorMovement::new(path));
+                        }
+                        ev_combat.send(CombatEvent::Movement {
+                            warrior: warrior_entity,
+                            to: ev.0,
+                            cost,
+                        });
                     }
                 }
             }
@@ -446,15 +551,56 @@ fn handle_warrior_action_on_click(
     }
 }
 
+/// Keep the warriors positions of the navigation grid up to date, the dead ones are removed on death
+fn update_nav_grid_occupants(
+    mut nav_grid: ResMut<NavGrid>,
+    warrior_query: Query<(Entity, &MapPosition), (With<Warrior>, Changed<MapPosition>)>,
+) {
+    for (entity, position) in warrior_query.iter() {
+        nav_grid.set_occupant(entity, *position);
+    }
+}
+
+/// Get the positions occupied by the other warriors and the enemies zone of control,
+/// as seen by the given moving warrior
+fn get_warrior_occupancy(
+    warrior_entity: Entity,
+    warriors: impl Iterator<Item = (Entity, MapPosition)>,
+    team_query: &Query<&Team, With<Warrior>>,
+) -> Occupancy {
+    let side = team_query.get(warrior_entity).ok().map(|team| team.side());
+    let mut occupancy = Occupancy {
+        zone_of_control_cost: ZONE_OF_CONTROL_COST,
+        ..Default::default()
+    };
+
+    for (entity, position) in warriors.filter(|(entity, _)| entity.ne(&warrior_entity)) {
+        let is_ally = side.is_some() && team_query.get(entity).ok().map(|team| team.side()) == side;
+
+        if !is_ally {
+            occupancy.enemies.push(position);
+        }
+
+        if is_ally && ALLIES_ARE_PASSABLE {
+            occupancy.passable.push(position);
+        } else {
+            occupancy.blocking.push(position);
+        }
+    }
+
+    occupancy
+}
+
 /// Reset warrior action & movement points at the end of their turn
 fn reset_warrior_attributes_on_turn_end(
     mut ev_turn_ended: EventReader<TurnEnd>,
-    mut q: Query<(&mut ActionPoints, &mut MovementPoints), With<Warrior>>,
+    mut q: Query<(&mut Attribute<ActionPoints>, &mut Attribute<MovementPoints>), With<Warrior>>,
 ) {
     for ev in ev_turn_ended.iter() {
-        let (mut ap, mut mp) = q.get_mut(ev.0).unwrap();
-        ap.reset();
-        mp.reset();
+        if let Ok((mut ap, mut mp)) = q.get_mut(ev.0) {
+            ap.rise_max();
+            mp.rise_max();
+        }
     }
 }
 
@@ -463,54 +609,66 @@ fn highlight_potential_action(
     turn: Res<Turn>,
     mouse_position: Res<MouseMapPosition>,
     selected_action: Res<SelectedAction>,
-    warrior_query: Query<&MapPosition, With<Warrior>>,
+    warrior_query: Query<(&MapPosition, &Actions), With<Warrior>>,
     mut map_query: MapQuery,
 ) {
-    if selected_action.0.is_none() {
-        return;
-    }
-
-    // TODO actions unmock
-    let (_, min_distance, max_distance) = match selected_action.0.unwrap() {
-        0 => ("Slash", 1, 2),
-        1 => ("Shoot", 3, 5),
-        2 => ("Cripple", 1, 2),
-        3 => ("Blind", 1, 1),
-        4 => ("Push", 1, 2),
-        5 => ("Teleport", 2, 5),
-        6 => ("Shield", 0, 0),
-        _ => ("Heal", 0, 1),
+    let warrior = turn
+        .get_current_warrior_entity()
+        .and_then(|entity| warrior_query.get(entity).ok());
+
+    let (warrior_position, action) = match (warrior, selected_action.0) {
+        (Some((position, actions)), Some(index)) => match actions.get(index) {
+            Some(action) => (position, action),
+            None => return,
+        },
+        _ => return,
     };
 
     let (_, map, _) = map_query.map_queryset.q1().single();
     let map_id = map.id;
     let highlight_layer_id = map.highlight_layer;
-    let map_width = map.width;
-    let map_height = map.height;
-
-    let warrior_entity = turn.get_current_warrior_entity().unwrap();
-    let warrior_position = warrior_query.get(warrior_entity).unwrap();
-    let surroundings = warrior_position.get_surrounding_positions(
-        min_distance,
-        max_distance,
-        map.width,
-        map.height,
-    );
-
-    for position in surroundings {
-        if map_query.line_of_sight_check(map_id, warrior_position, &position, map_width, map_height)
-        {
-            let alpha = mouse_position
-                .0
-                .filter(|mouse| mouse.eq(&position))
-                .map(|_| 0.9)
-                .unwrap_or(0.6);
+
+    let targetable_positions: Vec<(MapPosition, bool)> = action
+        .positions_in_range(warrior_position, &map_query.nav_grid)
+        .into_iter()
+        .map(|position| {
+            let in_sight = action.ignore_line_of_sight
+                || map_query.line_of_sight_check(warrior_position, &position);
+            (position, in_sight)
+        })
+        .collect();
+
+    // Positions in range but out of sight are dimmed
+    for (position, in_sight) in targetable_positions.iter() {
+        let tile_color = if *in_sight {
+            bevy::render::color::Color::from(color::HEALTH)
+                .set_a(0.6)
+                .as_rgba()
+        } else {
+            bevy::render::color::Color::from(color::OUT_OF_SIGHT)
+                .set_a(0.5)
+                .as_rgba()
+        };
+        map_query.update_tile_sprite_color(map_id, highlight_layer_id, position, tile_color);
+    }
+
+    // Preview the area of effect under the cursor
+    let hovered_target = mouse_position.0.filter(|mouse| {
+        targetable_positions
+            .iter()
+            .any(|(position, in_sight)| *in_sight && position.eq(mouse))
+    });
+
+    if let Some(target_position) = hovered_target {
+        let aoe_positions =
+            action.aoe_positions(warrior_position, &target_position, &map_query.nav_grid);
+        for position in aoe_positions {
             map_query.update_tile_sprite_color(
                 map_id,
                 highlight_layer_id,
                 &position,
                 bevy::render::color::Color::from(color::HEALTH)
-                    .set_a(alpha)
+                    .set_a(0.9)
                     .as_rgba(),
             );
         }
@@ -520,22 +678,56 @@ fn highlight_potential_action(
 fn despawn_warrior_on_death(
     mut commands: Commands,
     mut turn: ResMut<Turn>,
-    warrior_query: Query<(Entity, &Health), (With<Warrior>, Changed<Health>)>,
+    mut nav_grid: ResMut<NavGrid>,
+    mut ev_combat: EventWriter<CombatEvent>,
+    mut ev_turn_started: EventWriter<TurnStart>,
+    mut selected_action: ResMut<SelectedAction>,
+    mut warrior_query: Query<
+        (Entity, &Attribute<Health>, &mut AnimationController),
+        (With<Warrior>, Changed<Attribute<Health>>),
+    >,
 ) {
-    for (entity, health) in warrior_query.iter() {
-        if health.0.value == 0 {
-            let turn_index = turn.get_entity_index(entity);
-
-            if let Some(turn_index) = turn_index {
-                turn.order.remove(turn_index);
-                turn.order_index = if turn.order_index >= turn_index {
-                    turn.order_index.saturating_sub(1)
-                } else {
-                    turn.order_index
+    for (entity, health, mut animation) in warrior_query.iter_mut() {
+        if health.value() == 0 {
+            let is_current = turn.get_current_warrior_entity() == Some(entity);
+            let order = turn
+                .order
+                .iter()
+                .copied()
+                .filter(|e| e.ne(&entity))
+                .collect();
+            turn.set_order(order);
+
+            // The turn of a warrior dying while playing goes to the next one, a dead warrior's turn never ends
+            if is_current {
+                selected_action.0 = None;
+                if let Some(next) = turn.get_current_warrior_entity() {
+                    ev_turn_started.send(TurnStart(next));
                 }
             }
 
-            commands.entity(entity).despawn_recursive();
+            ev_combat.send(CombatEvent::Death { warrior: entity });
+            nav_grid.remove_occupant(entity);
+
+            // The warrior leaves the fight right away, its body stays until the end of its death animation
+            if animation.play("death") {
+                commands.entity(entity).remove::<Warrior>().insert(Dead);
+            } else {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Despawn the dead warriors once their death animation is over
+fn despawn_dead_warriors(
+    mut commands: Commands,
+    mut ev_finished: EventReader<AnimationFinished>,
+    dead_query: Query<(), With<Dead>>,
+) {
+    for ev in ev_finished.iter() {
+        if dead_query.get(ev.entity).is_ok() {
+            commands.entity(ev.entity).despawn_recursive();
         }
     }
 }
@@ -0,0 +1,160 @@
+use super::{Map, MapPosition, MapQuery, MouseMapPosition, Team, TeamSide, Tile, Warrior};
+use bevy::prelude::*;
+
+/// Placement phase state, each player drags its warriors on the spawn cells of its team
+#[derive(Default)]
+pub struct Placement {
+    /// The warrior currently dragged with the mouse
+    pub dragged: Option<Entity>,
+}
+
+/// Get the spawn layer of a team
+fn get_spawn_layer(map: &Map, side: TeamSide) -> u32 {
+    match side {
+        TeamSide::A => map.spawn_team_a_layer,
+        TeamSide::B => map.spawn_team_b_layer,
+    }
+}
+
+/// Move the warriors standing outside of their team spawn cells to the first free one
+pub fn place_warriors_on_spawn(
+    mut warrior_query: Query<(&Team, &mut MapPosition), (With<Warrior>, Without<Tile>)>,
+    mut map_query: MapQuery,
+) {
+    if map_query.map_queryset.q1().is_empty() {
+        return;
+    }
+
+    let (_, map, _) = map_query.map_queryset.q1().single();
+    let map_id = map.id;
+    let spawn_layers = [
+        (TeamSide::A, get_spawn_layer(map, TeamSide::A)),
+        (TeamSide::B, get_spawn_layer(map, TeamSide::B)),
+    ];
+
+    for (side, spawn_layer_id) in spawn_layers {
+        let spawn_positions = map_query.get_spawn_positions(map_id, spawn_layer_id);
+        let mut occupied: Vec<MapPosition> = warrior_query
+            .iter()
+            .map(|(_, &position)| position)
+            .collect();
+
+        for (team, mut position) in warrior_query.iter_mut() {
+            if team.side().ne(&side) || spawn_positions.contains(&*position) {
+                continue;
+            }
+
+            let free_position = spawn_positions
+                .iter()
+                .find(|spawn_position| !occupied.contains(spawn_position));
+
+            if let Some(&free_position) = free_position {
+                *position = free_position;
+                occupied.push(free_position);
+            }
+        }
+    }
+}
+
+/// Drag a warrior with the mouse and drop it on a free spawn cell of its team
+pub fn drag_and_drop_warriors(
+    buttons: Res<Input<MouseButton>>,
+    mouse_position: Res<MouseMapPosition>,
+    mut placement: ResMut<Placement>,
+    mut warrior_query: Query<(Entity, &Team, &mut MapPosition), (With<Warrior>, Without<Tile>)>,
+    mut map_query: MapQuery,
+) {
+    if buttons.just_pressed(MouseButton::Left) {
+        placement.dragged = mouse_position.0.and_then(|mouse_position| {
+            warrior_query
+                .iter()
+                .find(|(_, _, position)| **position == mouse_position)
+                .map(|(entity, ..)| entity)
+        });
+    }
+
+    if !buttons.just_released(MouseButton::Left) {
+        return;
+    }
+
+    let (dragged, drop_position) = match (placement.dragged.take(), mouse_position.0) {
+        (Some(dragged), Some(drop_position)) => (dragged, drop_position),
+        _ => return,
+    };
+
+    let side = match warrior_query.get(dragged) {
+        Ok((_, team, _)) => team.side(),
+        Err(_) => return,
+    };
+
+    let (_, map, _) = map_query.map_queryset.q1().single();
+    let map_id = map.id;
+    let spawn_layer_id = get_spawn_layer(map, side);
+
+    let is_spawn_position = map_query
+        .get_spawn_positions(map_id, spawn_layer_id)
+        .contains(&drop_position);
+    let is_free = !map_query.is_obstacle(&drop_position)
+        && warrior_query
+            .iter()
+            .all(|(_, _, position)| drop_position.ne(position));
+
+    if is_spawn_position && is_free {
+        if let Ok((_, _, mut position)) = warrior_query.get_mut(dragged) {
+            *position = drop_position;
+        }
+    }
+}
+
+/// Highlight the spawn cells of both teams, and where the dragged warrior can be dropped
+pub fn highlight_spawn_positions(
+    placement: Res<Placement>,
+    mouse_position: Res<MouseMapPosition>,
+    warrior_query: Query<(Entity, &Team, &MapPosition), With<Warrior>>,
+    mut map_query: MapQuery,
+) {
+    if map_query.map_queryset.q1().is_empty() {
+        return;
+    }
+
+    let (_, map, _) = map_query.map_queryset.q1().single();
+    let map_id = map.id;
+    let highlight_layer_id = map.highlight_layer;
+    let spawn_layers = [
+        (TeamSide::A, get_spawn_layer(map, TeamSide::A)),
+        (TeamSide::B, get_spawn_layer(map, TeamSide::B)),
+    ];
+
+    let dragged_side = placement
+        .dragged
+        .and_then(|entity| warrior_query.get(entity).ok())
+        .map(|(_, team, _)| team.side());
+
+    for (side, spawn_layer_id) in spawn_layers {
+        let is_dragging = dragged_side == Some(side);
+
+        for position in map_query.get_spawn_positions(map_id, spawn_layer_id) {
+            let occupant = warrior_query
+                .iter()
+                .find(|(_, _, warrior_position)| **warrior_position == position)
+                .map(|(entity, ..)| entity);
+
+            let alpha = match occupant {
+                Some(entity) if placement.dragged == Some(entity) => 0.9,
+                Some(_) => 0.8,
+                None if is_dragging && mouse_position.0 == Some(position) => 0.9,
+                None if is_dragging => 0.6,
+                None => 0.3,
+            };
+
+            map_query.update_tile_sprite_color(
+                map_id,
+                highlight_layer_id,
+                &position,
+                bevy::render::color::Color::from(side.color())
+                    .set_a(alpha)
+                    .as_rgba(),
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Set this environment variable to replay a match with a recorded seed
+const SEED_ENV_VAR: &str = "BROUTUS_SEED";
+
+/// Deterministic random numbers generator (splitmix64), used by all the gameplay randomness.
+/// The same seed always gives the same sequence, so a match can be replayed identically.
+pub struct GameRng {
+    seed: u64,
+    state: u64,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// The seed the generator started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Get a number between 0 (included) and 1 (excluded)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Roll a chance between 0 (never) and 1 (always)
+    pub fn roll(&mut self, chance: f32) -> bool {
+        self.next_f32() < chance
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(time_seed())
+    }
+}
+
+/// Get a seed from the current time
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Seed the random numbers generator of a new match, from the environment if set to replay a match
+pub fn seed_match_rng(mut rng: ResMut<GameRng>) {
+    let seed = std::env::var(SEED_ENV_VAR)
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(time_seed);
+
+    *rng = GameRng::new(seed);
+}
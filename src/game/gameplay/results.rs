@@ -0,0 +1,142 @@
+use super::{Attribute, CombatEvent, Dead, GameRng, GameState, Health, Team, TeamSide, Warrior};
+use bevy::prelude::*;
+
+/// Statistics of a warrior during the match, kept after its death
+#[derive(Debug, Clone)]
+pub struct WarriorStats {
+    pub name: String,
+    pub side: TeamSide,
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub healed: u32,
+}
+
+/// Statistics of every warrior of the current match, sorted by their spawning order
+#[derive(Default)]
+pub struct MatchStats {
+    warriors: Vec<(Entity, WarriorStats)>,
+}
+
+impl MatchStats {
+    pub fn get(&self, entity: Entity) -> Option<&WarriorStats> {
+        self.warriors
+            .iter()
+            .find(|(e, _)| e.eq(&entity))
+            .map(|(_, stats)| stats)
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut WarriorStats> {
+        self.warriors
+            .iter_mut()
+            .find(|(e, _)| e.eq(&entity))
+            .map(|(_, stats)| stats)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &WarriorStats)> {
+        self.warriors.iter().map(|(entity, stats)| (*entity, stats))
+    }
+
+    /// Credit the damages to the attacker, and debit them from the target
+    pub fn add_damage(&mut self, from: Entity, to: Entity, amount: u32) {
+        if let Some(stats) = self.get_mut(from) {
+            stats.damage_dealt += amount;
+        }
+        if let Some(stats) = self.get_mut(to) {
+            stats.damage_taken += amount;
+        }
+    }
+
+    /// Credit the heal to the healer
+    pub fn add_heal(&mut self, from: Entity, amount: u32) {
+        if let Some(stats) = self.get_mut(from) {
+            stats.healed += amount;
+        }
+    }
+}
+
+/// The outcome of the last match
+pub struct MatchResult {
+    /// The winning team, none if both teams died at the same time
+    pub winner: Option<TeamSide>,
+
+    /// The turn count when the match ended
+    pub turns: usize,
+
+    /// The seed of the random numbers of the match, to replay it
+    pub seed: u64,
+}
+
+/// Register every warrior of the fight in new match statistics
+pub fn start_match_stats(
+    mut stats: ResMut<MatchStats>,
+    warrior_query: Query<(Entity, &Name, &Team), With<Warrior>>,
+) {
+    stats.warriors = warrior_query
+        .iter()
+        .map(|(entity, name, team)| {
+            let warrior_stats = WarriorStats {
+                name: name.as_str().to_string(),
+                side: team.side(),
+                damage_dealt: 0,
+                damage_taken: 0,
+                healed: 0,
+            };
+            (entity, warrior_stats)
+        })
+        .collect();
+}
+
+/// Count the damages and heals of the combat events in the match statistics
+pub fn record_match_stats(mut stats: ResMut<MatchStats>, mut ev_combat: EventReader<CombatEvent>) {
+    for ev in ev_combat.iter() {
+        match *ev {
+            CombatEvent::Damage {
+                caster,
+                target,
+                amount,
+                ..
+            } => stats.add_damage(caster, target, amount),
+            CombatEvent::Heal { caster, amount, .. } => stats.add_heal(caster, amount),
+            _ => (),
+        }
+    }
+}
+
+/// End the match as soon as a team has no warrior alive
+pub fn detect_match_end(
+    mut commands: Commands,
+    mut game_state: ResMut<State<GameState>>,
+    turn: Res<super::Turn>,
+    rng: Res<GameRng>,
+    warrior_query: Query<(&Team, &Attribute<Health>), With<Warrior>>,
+) {
+    let is_alive = |side: TeamSide| {
+        warrior_query
+            .iter()
+            .any(|(team, health)| team.side() == side && health.value() > 0)
+    };
+
+    let winner = match (is_alive(TeamSide::A), is_alive(TeamSide::B)) {
+        (true, true) => return,
+        (true, false) => Some(TeamSide::A),
+        (false, true) => Some(TeamSide::B),
+        (false, false) => None,
+    };
+
+    commands.insert_resource(MatchResult {
+        winner,
+        turns: turn.current + 1,
+        seed: rng.seed(),
+    });
+    game_state.set(GameState::Results).unwrap();
+}
+
+/// Despawn the warriors still standing or dying when leaving the results screen
+pub fn despawn_warriors(
+    mut commands: Commands,
+    warrior_query: Query<Entity, Or<(With<Warrior>, With<Dead>)>>,
+) {
+    for entity in warrior_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
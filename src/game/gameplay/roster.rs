@@ -0,0 +1,112 @@
+use super::{TeamSide, WarriorAsset};
+use bevy::prelude::*;
+
+/// Number of warriors each team has to pick
+pub const ROSTER_SIZE: usize = 3;
+
+/// Warriors picked by each team during the Picking state, spawned once the Arena starts
+#[derive(Default)]
+pub struct TeamRoster {
+    team_a: TeamPicks,
+    team_b: TeamPicks,
+}
+
+#[derive(Default)]
+struct TeamPicks {
+    warriors: Vec<Handle<WarriorAsset>>,
+    confirmed: bool,
+}
+
+impl TeamRoster {
+    fn picks(&self, side: TeamSide) -> &TeamPicks {
+        match side {
+            TeamSide::A => &self.team_a,
+            TeamSide::B => &self.team_b,
+        }
+    }
+
+    fn picks_mut(&mut self, side: TeamSide) -> &mut TeamPicks {
+        match side {
+            TeamSide::A => &mut self.team_a,
+            TeamSide::B => &mut self.team_b,
+        }
+    }
+
+    /// Get the warriors picked by a team, in their picking order
+    pub fn get(&self, side: TeamSide) -> &[Handle<WarriorAsset>] {
+        &self.picks(side).warriors
+    }
+
+    pub fn is_picked(&self, side: TeamSide, warrior: &Handle<WarriorAsset>) -> bool {
+        self.get(side).contains(warrior)
+    }
+
+    /// Get the teams which picked the warrior
+    pub fn picked_by(&self, warrior: &Handle<WarriorAsset>) -> Vec<TeamSide> {
+        [TeamSide::A, TeamSide::B]
+            .into_iter()
+            .filter(|&side| self.is_picked(side, warrior))
+            .collect()
+    }
+
+    /// A warrior can only be picked once per team, by a team which roster is neither full nor confirmed
+    pub fn can_pick(&self, side: TeamSide, warrior: &Handle<WarriorAsset>) -> bool {
+        !self.is_confirmed(side) && !self.is_complete(side) && !self.is_picked(side, warrior)
+    }
+
+    /// Add the warrior to the team if possible, returning whether it has been picked
+    pub fn pick(&mut self, side: TeamSide, warrior: &Handle<WarriorAsset>) -> bool {
+        if !self.can_pick(side, warrior) {
+            return false;
+        }
+
+        self.picks_mut(side).warriors.push(warrior.clone());
+        true
+    }
+
+    /// Add the warrior to the team if possible, remove it if it was already picked by the team
+    pub fn toggle(&mut self, side: TeamSide, warrior: &Handle<WarriorAsset>) {
+        if self.is_confirmed(side) {
+            return;
+        }
+
+        if self.is_picked(side, warrior) {
+            self.picks_mut(side)
+                .warriors
+                .retain(|picked| picked.ne(warrior));
+        } else {
+            self.pick(side, warrior);
+        }
+    }
+
+    pub fn is_complete(&self, side: TeamSide) -> bool {
+        self.get(side).len() == ROSTER_SIZE
+    }
+
+    pub fn is_confirmed(&self, side: TeamSide) -> bool {
+        self.picks(side).confirmed
+    }
+
+    /// Lock the team picks, only a complete roster can be confirmed
+    pub fn confirm(&mut self, side: TeamSide) {
+        if self.is_complete(side) {
+            self.picks_mut(side).confirmed = true;
+        }
+    }
+
+    /// Unlock the team picks so they can be edited again
+    pub fn unconfirm(&mut self, side: TeamSide) {
+        self.picks_mut(side).confirmed = false;
+    }
+
+    /// Remove all picks of both teams
+    pub fn clear(&mut self) {
+        self.team_a = TeamPicks::default();
+        self.team_b = TeamPicks::default();
+    }
+
+    /// Both teams have to confirm their complete roster to fight
+    pub fn is_ready(&self) -> bool {
+        self.is_confirmed(TeamSide::A) && self.is_confirmed(TeamSide::B)
+    }
+}
@@ -0,0 +1,142 @@
+use super::{
+    inflict_damage, ActionPoints, Attribute, CombatEvent, Erosion, Health, MovementPoints,
+    SelectedAction, Shield, StatusEffects, StatusKind, Turn, TurnEnd, TurnSkip, TurnStart, Warrior,
+};
+use bevy::prelude::*;
+
+/// Apply the damages, heals and debuffs of the statuses of a warrior at the start of its turn
+pub fn apply_status_effects(
+    mut ev_turn_started: EventReader<TurnStart>,
+    mut ev_combat: EventWriter<CombatEvent>,
+    mut q: Query<
+        (
+            &mut Attribute<Health>,
+            &mut Erosion,
+            &mut Attribute<Shield>,
+            &mut Attribute<ActionPoints>,
+            &mut Attribute<MovementPoints>,
+            &mut StatusEffects,
+        ),
+        With<Warrior>,
+    >,
+) {
+    for ev in ev_turn_started.iter() {
+        let target = ev.0;
+        let (
+            mut health,
+            mut erosion,
+            mut shield,
+            mut action_points,
+            mut movement_points,
+            mut status_effects,
+        ) = match q.get_mut(target) {
+            Ok(warrior) => warrior,
+            Err(_) => continue,
+        };
+
+        let statuses: Vec<_> = status_effects.iter().copied().collect();
+        for status in statuses {
+            let caster = status.caster;
+            match status.kind {
+                StatusKind::DamageOverTime { amount, erode } => {
+                    let (lost, absorbed) = inflict_damage(
+                        amount,
+                        erode,
+                        &mut health,
+                        &mut erosion,
+                        &mut shield,
+                        &mut status_effects,
+                    );
+                    ev_combat.send(CombatEvent::Damage {
+                        caster,
+                        target,
+                        amount: lost,
+                        absorbed,
+                        is_crit: false,
+                    });
+                }
+                StatusKind::HealOverTime { amount } => {
+                    let overflow = health.rise(amount);
+                    ev_combat.send(CombatEvent::Heal {
+                        caster,
+                        target,
+                        amount: amount - overflow,
+                    });
+                }
+                StatusKind::ActionPointsDebuff { amount } => {
+                    let lost = amount - action_points.drop(amount);
+                    ev_combat.send(CombatEvent::ActionPointsLost {
+                        caster,
+                        target,
+                        amount: lost,
+                        stolen: false,
+                    });
+                }
+                StatusKind::MovementPointsDebuff { amount } => {
+                    let lost = amount - movement_points.drop(amount);
+                    ev_combat.send(CombatEvent::MovementPointsLost {
+                        caster,
+                        target,
+                        amount: lost,
+                        stolen: false,
+                    });
+                }
+                StatusKind::Shield { .. } | StatusKind::Stun => (),
+            }
+        }
+
+        status_effects.start_turn();
+    }
+}
+
+/// Count down the statuses of a warrior at the end of its turn, the expired shields are removed
+pub fn expire_status_effects(
+    mut ev_turn_ended: EventReader<TurnEnd>,
+    mut q: Query<(&mut Attribute<Shield>, &mut StatusEffects), With<Warrior>>,
+) {
+    for ev in ev_turn_ended.iter() {
+        if let Ok((mut shield, mut status_effects)) = q.get_mut(ev.0) {
+            for status in status_effects.tick() {
+                if let StatusKind::Shield { amount } = status.kind {
+                    shield.drop(amount);
+                }
+            }
+        }
+    }
+}
+
+/// A warrior stunned when its turn starts skips it
+pub fn detect_stunned_warrior_turn(
+    mut ev_turn_started: EventReader<TurnStart>,
+    mut ev_turn_skipped: EventWriter<TurnSkip>,
+    q: Query<&StatusEffects, With<Warrior>>,
+) {
+    for ev in ev_turn_started.iter() {
+        if let Ok(status_effects) = q.get(ev.0) {
+            if status_effects.is_stunned() {
+                ev_turn_skipped.send(TurnSkip(ev.0));
+            }
+        }
+    }
+}
+
+/// Give the turn of a skipping warrior to the next one
+pub fn skip_stunned_warrior_turn(
+    mut turn: ResMut<Turn>,
+    mut selected_action: ResMut<SelectedAction>,
+    mut ev_turn_skipped: EventReader<TurnSkip>,
+    ev_turn_started: EventWriter<TurnStart>,
+    ev_turn_ended: EventWriter<TurnEnd>,
+) {
+    let current = turn.get_current_warrior_entity();
+    let is_skipped = ev_turn_skipped
+        .iter()
+        .filter(|ev| Some(ev.0) == current)
+        .count()
+        > 0;
+
+    if is_skipped {
+        selected_action.0 = None;
+        turn.set_next(ev_turn_started, ev_turn_ended);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::game::color;
 use crate::game::color::Color;
 use bevy::prelude::*;
 
@@ -18,14 +19,33 @@ impl Team {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TeamSide {
     A,
     B,
 }
 
+impl TeamSide {
+    /// Get the default color of the team side
+    pub fn color(&self) -> Color {
+        match self {
+            Self::A => color::TEAM_A_COLOR,
+            Self::B => color::TEAM_B_COLOR,
+        }
+    }
+}
+
 impl Default for TeamSide {
     fn default() -> Self {
         Self::A
     }
 }
+
+impl std::fmt::Display for TeamSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::A => write!(f, "Team A"),
+            Self::B => write!(f, "Team B"),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::{
+    Action, ActionTargetQuery, AnimationController, CombatEvent, GameRng, MapPosition, NavGrid,
+    Warrior, WarriorMovement,
+};
+use bevy::prelude::*;
+
+/// Set this environment variable to skip the animations of the actions and movements
+const FAST_MODE_ENV_VAR: &str = "BROUTUS_FAST_MODE";
+
+/// Resolve actions and movements at once, without waiting for their animations
+pub struct FastMode(pub bool);
+
+impl Default for FastMode {
+    fn default() -> Self {
+        let enabled = std::env::var(FAST_MODE_ENV_VAR)
+            .map(|value| value == "1" || value == "true")
+            .unwrap_or(false);
+
+        Self(enabled)
+    }
+}
+
+/// The warriors still walking or acting, the turn can't go on until they are done
+pub type BusyWarriorQuery<'w, 's> =
+    Query<'w, 's, (), Or<(With<WarriorMovement>, With<PendingAction>)>>;
+
+/// An action cast by a warrior, waiting for the impact frame of its animation to apply its effects
+#[derive(Component)]
+pub struct PendingAction {
+    action: Action,
+    to_position: MapPosition,
+    applied: bool,
+}
+
+impl PendingAction {
+    pub fn new(action: Action, to_position: MapPosition) -> Self {
+        Self {
+            action,
+            to_position,
+            applied: false,
+        }
+    }
+
+    /// Get the animation the caster plays while casting the action
+    pub fn animation_key(&self) -> &str {
+        self.action.animation_key.as_str()
+    }
+}
+
+/// Apply the effects of the pending actions on their impact frame,
+/// the caster gets back control once its animation is over
+pub fn resolve_pending_actions(
+    mut commands: Commands,
+    mut pending_query: Query<(Entity, &mut PendingAction, &AnimationController), With<Warrior>>,
+    mut warrior_query: ActionTargetQuery,
+    nav_grid: Res<NavGrid>,
+    mut rng: ResMut<GameRng>,
+    mut ev_combat: EventWriter<CombatEvent>,
+) {
+    for (caster, mut pending, animation) in pending_query.iter_mut() {
+        let is_playing = animation.is_playing(pending.animation_key());
+
+        if !pending.applied && (!is_playing || animation.frame() >= pending.action.impact_frame) {
+            let to_position = pending.to_position;
+            pending.action.execute(
+                caster,
+                &to_position,
+                &nav_grid,
+                &mut warrior_query,
+                &mut rng,
+                &mut ev_combat,
+            );
+            pending.applied = true;
+        }
+
+        if pending.applied && !is_playing {
+            commands.entity(caster).remove::<PendingAction>();
+        }
+    }
+}
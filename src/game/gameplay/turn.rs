@@ -1,10 +1,13 @@
-use super::SelectedAction;
+use super::{Attribute, BusyWarriorQuery, Initiative, SelectedAction, Team, TeamSide, Warrior};
 use bevy::prelude::*;
 
 pub struct TurnStart(pub Entity);
 
 pub struct TurnEnd(pub Entity);
 
+/// The warrior can't play the turn it just started
+pub struct TurnSkip(pub Entity);
+
 #[derive(Default)]
 pub struct Turn {
     /// Current turn, is incremented the order has been consumed
@@ -20,17 +23,29 @@ pub struct Turn {
 impl Turn {
     /// Move forward in the turn system, select the next warrior, incrementing the turn count if necessary
     pub fn set_next(&mut self, mut start: EventWriter<TurnStart>, mut end: EventWriter<TurnEnd>) {
-        end.send(TurnEnd(self.get_current_warrior_entity().unwrap()));
+        let current = match self.get_current_warrior_entity() {
+            Some(entity) => entity,
+            None => return,
+        };
+
+        end.send(TurnEnd(current));
         self.order_index = self.get_next_order_index();
         self.current = if self.order_index == 0 {
             self.current + 1
         } else {
             self.current
         };
-        start.send(TurnStart(self.get_current_warrior_entity().unwrap()));
+        if let Some(next) = self.get_current_warrior_entity() {
+            start.send(TurnStart(next));
+        }
     }
 
+    /// Get the order index of the next warrior, 0 when the order is empty
     pub fn get_next_order_index(&self) -> usize {
+        if self.order.is_empty() {
+            return 0;
+        }
+
         (self.order_index + 1) % self.order.len()
     }
 
@@ -41,6 +56,93 @@ impl Turn {
     pub fn get_entity_index(&self, entity: Entity) -> Option<usize> {
         self.order.iter().position(|e| e.eq(&entity))
     }
+
+    /// Replace the turn order, the current warrior keeps playing, or the next one still in the order if removed
+    pub fn set_order(&mut self, order: Vec<Entity>) {
+        let order_index = self
+            .order
+            .iter()
+            .cycle()
+            .skip(self.order_index)
+            .take(self.order.len())
+            .find_map(|entity| order.iter().position(|e| e.eq(entity)))
+            .unwrap_or(0);
+
+        self.order = order;
+        self.order_index = order_index;
+    }
+}
+
+/// Sort warriors by initiative, the highest first, equal initiatives are broken by alternating teams
+pub fn build_turn_order(mut warriors: Vec<(Entity, TeamSide, u32)>) -> Vec<Entity> {
+    warriors.sort_by(|(left, _, left_initiative), (right, _, right_initiative)| {
+        right_initiative
+            .cmp(left_initiative)
+            .then_with(|| left.id().cmp(&right.id()))
+    });
+
+    let mut order = Vec::with_capacity(warriors.len());
+    let mut last_side = None;
+
+    while !warriors.is_empty() {
+        let (_, _, initiative) = warriors[0];
+        let index = warriors
+            .iter()
+            .take_while(|(_, _, other_initiative)| initiative.eq(other_initiative))
+            .position(|(_, side, _)| Some(*side).ne(&last_side))
+            .unwrap_or(0);
+
+        let (entity, side, _) = warriors.remove(index);
+        order.push(entity);
+        last_side = Some(side);
+    }
+
+    order
+}
+
+/// Build the turn order of the warriors in the arena when the fight starts, and start the first turn
+pub fn start_turns(
+    mut turn: ResMut<Turn>,
+    mut ev_turn_started: EventWriter<TurnStart>,
+    warrior_query: Query<(Entity, &Team, &Attribute<Initiative>), With<Warrior>>,
+) {
+    let warriors = warrior_query
+        .iter()
+        .map(|(entity, team, initiative)| (entity, team.side(), initiative.value()))
+        .collect();
+
+    *turn = Turn {
+        order: build_turn_order(warriors),
+        ..Default::default()
+    };
+
+    if let Some(first) = turn.get_current_warrior_entity() {
+        ev_turn_started.send(TurnStart(first));
+    }
+}
+
+/// Rebuild the turn order when a warrior is summoned or its initiative changes
+pub fn update_turn_order(
+    mut turn: ResMut<Turn>,
+    changed_query: Query<
+        (),
+        (
+            With<Warrior>,
+            Or<(Added<Warrior>, Changed<Attribute<Initiative>>)>,
+        ),
+    >,
+    warrior_query: Query<(Entity, &Team, &Attribute<Initiative>), With<Warrior>>,
+) {
+    if changed_query.is_empty() {
+        return;
+    }
+
+    let warriors = warrior_query
+        .iter()
+        .map(|(entity, team, initiative)| (entity, team.side(), initiative.value()))
+        .collect();
+
+    turn.set_order(build_turn_order(warriors));
 }
 
 pub struct TurnTimer(pub Timer);
@@ -58,7 +160,13 @@ pub fn run_turn_timer(
     ev_turn_started: EventWriter<TurnStart>,
     ev_turn_ended: EventWriter<TurnEnd>,
     mut selected_action: ResMut<SelectedAction>,
+    busy_query: BusyWarriorQuery,
 ) {
+    // The timer waits for the warriors to be done walking and acting
+    if !busy_query.is_empty() {
+        return;
+    }
+
     if timer.0.tick(time.delta()).just_finished() {
         selected_action.0 = None;
         turn.set_next(ev_turn_started, ev_turn_ended);
@@ -1,11 +1,41 @@
+use super::super::{CombatEvent, GameRng, MapPosition, NavGrid, Tile};
 use super::*;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Damages taken by a pushed warrior for each cell it could not be pushed on
+const PUSH_COLLISION_DAMAGES: u32 = 20;
+
+/// The index of the current warrior's action selected by the player, if any
+#[derive(Default)]
+pub struct SelectedAction(pub Option<usize>);
+
 /// NewType representing a Warrior's action collection
 #[derive(Debug, Component)]
 pub struct Actions(Vec<Action>);
 
+impl Actions {
+    pub fn new(actions: Vec<Action>) -> Self {
+        Self(actions)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Action> {
+        self.0.get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Action> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 impl Default for Actions {
     fn default() -> Self {
         Self(Vec::new())
@@ -13,7 +43,7 @@ impl Default for Actions {
 }
 
 /// A Warrior action is usable in Arena mode, consuming ActionPoints
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Action {
     pub name: String,
     pub icon_key: String,
@@ -22,71 +52,395 @@ pub struct Action {
     pub aoe: ActionAoe,
     pub range: ActionRange,
     pub effects: Vec<ActionEffect>,
+
+    /// The area of effect stops on obstacles
+    #[serde(default)]
+    pub aoe_stops_on_obstacles: bool,
+
+    /// The action can be cast on positions out of sight
+    #[serde(default)]
+    pub ignore_line_of_sight: bool,
+
+    /// Frame of the caster animation when the effects apply
+    #[serde(default = "default_impact_frame")]
+    pub impact_frame: usize,
+}
+
+fn default_impact_frame() -> usize {
+    2
 }
 
+/// The warriors an action can change, with all the attributes its effects can reach
+pub type ActionTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut MapPosition,
+        &'static mut Attribute<Health>,
+        &'static mut Erosion,
+        &'static mut Attribute<Shield>,
+        &'static mut StatusEffects,
+        &'static mut Attribute<ActionPoints>,
+        &'static mut Attribute<MovementPoints>,
+    ),
+    (With<Warrior>, Without<Tile>),
+>;
+
 impl Action {
+    /// Check if the action can be cast on the target position, in range and in sight
+    pub fn can_target(
+        &self,
+        from_position: &MapPosition,
+        to_position: &MapPosition,
+        nav_grid: &NavGrid,
+    ) -> bool {
+        self.is_in_range(from_position, to_position, nav_grid)
+            && self.is_in_sight(from_position, to_position, nav_grid)
+    }
+
+    /// Get all the positions in range of the action, in sight or not
+    pub fn positions_in_range(
+        &self,
+        from_position: &MapPosition,
+        nav_grid: &NavGrid,
+    ) -> Vec<MapPosition> {
+        (0..nav_grid.height())
+            .flat_map(|y| (0..nav_grid.width()).map(move |x| MapPosition::new(x, y)))
+            .filter(|position| self.is_in_range(from_position, position, nav_grid))
+            .collect()
+    }
+
+    /// Check if the target position is in range and not an obstacle
+    fn is_in_range(
+        &self,
+        from_position: &MapPosition,
+        to_position: &MapPosition,
+        nav_grid: &NavGrid,
+    ) -> bool {
+        self.range.can_reach(from_position, to_position) && !nav_grid.is_blocked(to_position)
+    }
+
+    /// Check if no obstacle nor warrior blocks the sight to the target position, unless the action ignores it
+    fn is_in_sight(
+        &self,
+        from_position: &MapPosition,
+        to_position: &MapPosition,
+        nav_grid: &NavGrid,
+    ) -> bool {
+        self.ignore_line_of_sight || nav_grid.line_of_sight_check(from_position, to_position)
+    }
+
+    /// Get the positions hit by the action when targeting the given position
+    pub fn aoe_positions(
+        &self,
+        from_position: &MapPosition,
+        to_position: &MapPosition,
+        nav_grid: &NavGrid,
+    ) -> Vec<MapPosition> {
+        self.aoe.positions(
+            from_position,
+            to_position,
+            nav_grid.width(),
+            nav_grid.height(),
+            |position| self.aoe_stops_on_obstacles && nav_grid.is_blocked(position),
+        )
+    }
+
     /// Execute all action effects one by one
     pub fn execute(
         &self,
-        from_position: &super::super::MapPosition,
-        to_position: &super::super::MapPosition,
-        map_query: &mut super::super::MapQuery,
-        warrior_query: &mut Query<
-            (
-                &Name,
-                &mut super::super::MapPosition,
-                &mut Attribute<Health>,
-                &mut Attribute<Shield>,
-                &mut Attribute<ActionPoints>,
-                &mut Attribute<MovementPoints>,
-            ),
-            With<Warrior>,
-        >,
+        caster: Entity,
+        to_position: &MapPosition,
+        nav_grid: &NavGrid,
+        warrior_query: &mut ActionTargetQuery,
+        rng: &mut GameRng,
+        ev_combat: &mut EventWriter<CombatEvent>,
     ) {
-        let hit_positions = match self.aoe {
-            ActionAoe::Cell => vec![to_position],
-            _ => vec![],
+        let from_position = match warrior_query.get(caster) {
+            Ok((_, position, ..)) => *position,
+            Err(_) => return,
         };
+        let from_position = &from_position;
+
+        let hit_positions = self.aoe_positions(from_position, to_position, nav_grid);
+
+        // The warriors standing in the area of effect when the action starts
+        let targets: Vec<Entity> = warrior_query
+            .iter()
+            .filter(|(_, position, ..)| hit_positions.contains(position))
+            .map(|(entity, ..)| entity)
+            .collect();
+
+        for effect in self.effects.iter() {
+            match *effect {
+                ActionEffect::Nothing => (),
+                ActionEffect::Damage {
+                    amount,
+                    erode,
+                    crit_mult,
+                    crit_chance,
+                } => {
+                    for &target in targets.iter() {
+                        if let Ok((
+                            _,
+                            _,
+                            mut health,
+                            mut erosion,
+                            mut shield,
+                            mut status_effects,
+                            ..,
+                        )) = warrior_query.get_mut(target)
+                        {
+                            let is_crit = rng.roll(crit_chance);
+                            let mutl = if is_crit { crit_mult } else { 1.0 };
+                            let final_amount = (amount as f32 * mutl).round() as u32;
+
+                            let (lost, absorbed) = inflict_damage(
+                                final_amount,
+                                erode,
+                                &mut health,
+                                &mut erosion,
+                                &mut shield,
+                                &mut status_effects,
+                            );
+                            ev_combat.send(CombatEvent::Damage {
+                                caster,
+                                target,
+                                amount: lost,
+                                absorbed,
+                                is_crit,
+                            });
+                        }
+                    }
+                }
+                ActionEffect::DamageOverTime {
+                    amount,
+                    erode,
+                    duration,
+                } => {
+                    let kind = StatusKind::DamageOverTime { amount, erode };
+                    add_status(caster, &targets, kind, duration, warrior_query);
+                }
+                ActionEffect::HealOverTime { amount, duration } => {
+                    let kind = StatusKind::HealOverTime { amount };
+                    add_status(caster, &targets, kind, duration, warrior_query);
+                }
+                ActionEffect::ActionPointsDebuff { amount, duration } => {
+                    let kind = StatusKind::ActionPointsDebuff { amount };
+                    add_status(caster, &targets, kind, duration, warrior_query);
+                }
+                ActionEffect::MovementPointsDebuff { amount, duration } => {
+                    let kind = StatusKind::MovementPointsDebuff { amount };
+                    add_status(caster, &targets, kind, duration, warrior_query);
+                }
+                ActionEffect::Stun { duration } => {
+                    add_status(caster, &targets, StatusKind::Stun, duration, warrior_query);
+                }
+                ActionEffect::Heal { amount } => {
+                    for &target in targets.iter() {
+                        if let Ok((_, _, mut health, ..)) = warrior_query.get_mut(target) {
+                            let overflow = health.rise(amount);
+                            ev_combat.send(CombatEvent::Heal {
+                                caster,
+                                target,
+                                amount: amount - overflow,
+                            });
+                        }
+                    }
+                }
+                ActionEffect::RestoreErosion { amount } => {
+                    for &target in targets.iter() {
+                        if let Ok((_, _, mut health, mut erosion, ..)) =
+                            warrior_query.get_mut(target)
+                        {
+                            let restored = restore_eroded_health(amount, &mut health, &mut erosion);
+                            ev_combat.send(CombatEvent::ErosionRestored {
+                                caster,
+                                target,
+                                amount: restored,
+                            });
+                        }
+                    }
+                }
+                ActionEffect::Shield { amount, duration } => {
+                    for &target in targets.iter() {
+                        if let Ok((_, _, _, _, mut shield, mut status_effects, ..)) =
+                            warrior_query.get_mut(target)
+                        {
+                            let gained = amount - shield.rise(amount);
+                            if let Some(duration) = duration {
+                                let kind = StatusKind::Shield { amount: gained };
+                                status_effects.add(Status::new(caster, kind, duration));
+                            }
+                            ev_combat.send(CombatEvent::ShieldGained {
+                                caster,
+                                target,
+                                amount: gained,
+                            });
+                        }
+                    }
+                }
+                ActionEffect::RemoveActionPoints { amount }
+                | ActionEffect::StealActionPoints { amount } => {
+                    let stolen = matches!(effect, ActionEffect::StealActionPoints { .. });
+                    let mut total = 0;
+
+                    for &target in targets.iter() {
+                        if let Ok((_, _, _, _, _, _, mut action_points, _)) =
+                            warrior_query.get_mut(target)
+                        {
+                            let lost = amount - action_points.drop(amount);
+                            total += lost;
+                            ev_combat.send(CombatEvent::ActionPointsLost {
+                                caster,
+                                target,
+                                amount: lost,
+                                stolen,
+                            });
+                        }
+                    }
+
+                    // The caster gains what has been stolen
+                    if stolen {
+                        if let Ok((_, _, _, _, _, _, mut action_points, _)) =
+                            warrior_query.get_mut(caster)
+                        {
+                            action_points.rise(total);
+                        }
+                    }
+                }
+                ActionEffect::RemoveMovementPoints { amount }
+                | ActionEffect::StealMovementPoints { amount } => {
+                    let stolen = matches!(effect, ActionEffect::StealMovementPoints { .. });
+                    let mut total = 0;
+
+                    for &target in targets.iter() {
+                        if let Ok((.., mut movement_points)) = warrior_query.get_mut(target) {
+                            let lost = amount - movement_points.drop(amount);
+                            total += lost;
+                            ev_combat.send(CombatEvent::MovementPointsLost {
+                                caster,
+                                target,
+                                amount: lost,
+                                stolen,
+                            });
+                        }
+                    }
+
+                    // The caster gains what has been stolen
+                    if stolen {
+                        if let Ok((.., mut movement_points)) = warrior_query.get_mut(caster) {
+                            movement_points.rise(total);
+                        }
+                    }
+                }
+                ActionEffect::TeleportSelf => {
+                    let is_occupied = warrior_query
+                        .iter()
+                        .any(|(_, position, ..)| *position == *to_position);
 
-        for hit_position in hit_positions {
-            // Process warriors on the given position
-            for (_, mut position, mut health, ..) in warrior_query.iter_mut() {
-                if position.ne(hit_position) {
-                    continue;
-                }
-
-                for effect in self.effects.iter() {
-                    // Implementation example
-                    if let ActionEffect::Damage {
-                        amount,
-                        erode,
-                        crit_chance,
-                        crit_mult,
-                    } = effect
-                    {
-                        let is_crit = *crit_chance >= 1.0;
-                        let mutl = if is_crit { *crit_mult } else { 1.0 };
-                        let final_amount = (*amount as f32 * mutl).round() as u32;
-
-                        health.erode(final_amount, *erode);
-                        health.drop(final_amount);
+                    if !is_occupied && !nav_grid.is_blocked(to_position) {
+                        if let Ok((_, mut position, ..)) = warrior_query.get_mut(caster) {
+                            *position = *to_position;
+                            ev_combat.send(CombatEvent::Teleport {
+                                warrior: caster,
+                                to: *to_position,
+                            });
+                        }
+                    }
+                }
+                ActionEffect::TeleportSwitch => {
+                    let target = targets.iter().copied().find(|target| target.ne(&caster));
+                    let positions = target.and_then(|target| {
+                        let (_, caster_position, ..) = warrior_query.get(caster).ok()?;
+                        let (_, target_position, ..) = warrior_query.get(target).ok()?;
+                        Some((target, *caster_position, *target_position))
+                    });
+
+                    if let Some((target, caster_position, target_position)) = positions {
+                        for (warrior, to) in [(caster, target_position), (target, caster_position)]
+                        {
+                            if let Ok((_, mut position, ..)) = warrior_query.get_mut(warrior) {
+                                *position = to;
+                                ev_combat.send(CombatEvent::Teleport { warrior, to });
+                            }
+                        }
                     }
+                }
+                ActionEffect::PushLinear { distance } | ActionEffect::PushDiagonal { distance } => {
+                    for &target in targets.iter() {
+                        let position = match warrior_query.get(target) {
+                            Ok((_, position, ..)) => *position,
+                            Err(_) => continue,
+                        };
+
+                        let path = match effect {
+                            ActionEffect::PushLinear { .. } => {
+                                from_position.direction_to(to_position).map(|direction| {
+                                    position.unchecked_path_torward(direction, distance)
+                                })
+                            }
+                            _ => from_position.diagonal_step_to(to_position).map(|step| {
+                                position.unchecked_diagonal_path_torward(step, distance)
+                            }),
+                        }
+                        .unwrap_or_default();
+
+                        let occupied: Vec<MapPosition> = warrior_query
+                            .iter()
+                            .filter(|(entity, ..)| entity.ne(&target))
+                            .map(|(_, position, ..)| *position)
+                            .collect();
+
+                        // The pushed warrior stops on obstacles and warriors
+                        let travelled = path
+                            .iter()
+                            .take_while(|next_position| {
+                                !occupied.contains(next_position)
+                                    && !nav_grid.is_blocked(next_position)
+                            })
+                            .count();
+                        let final_position = travelled
+                            .checked_sub(1)
+                            .map(|index| path[index])
+                            .unwrap_or(position);
 
-                    // Implementation example
-                    if let ActionEffect::PushLinear { distance } = effect {
-                        if let Some(direction) = from_position.direction_to(&to_position) {
-                            let path = position.unchecked_path_torward(direction, *distance);
-                            let mut path_iter = path.iter();
-                            while let Some(next_position) = path_iter.next() {
-                                // TODO retreive these values nicely
-                                if map_query.is_obstacle(0u32, next_position, 20, 12) {
-                                    let remaining_length = path_iter.count() as u32;
-                                    let damages = 20 * remaining_length;
-                                    health.drop(damages);
-                                    break;
-                                }
-                                position.x = next_position.x;
-                                position.y = next_position.y;
+                        if let Ok((
+                            _,
+                            mut position,
+                            mut health,
+                            mut erosion,
+                            mut shield,
+                            mut status_effects,
+                            ..,
+                        )) = warrior_query.get_mut(target)
+                        {
+                            *position = final_position;
+                            ev_combat.send(CombatEvent::Push {
+                                caster,
+                                target,
+                                to: final_position,
+                            });
+
+                            // The collision hurts for each cell the warrior could not be pushed
+                            let remaining_length = (path.len() - travelled) as u32;
+                            if remaining_length > 0 {
+                                let (lost, absorbed) = inflict_damage(
+                                    PUSH_COLLISION_DAMAGES * remaining_length,
+                                    0.0,
+                                    &mut health,
+                                    &mut erosion,
+                                    &mut shield,
+                                    &mut status_effects,
+                                );
+                                ev_combat.send(CombatEvent::Damage {
+                                    caster,
+                                    target,
+                                    amount: lost,
+                                    absorbed,
+                                    is_crit: false,
+                                });
                             }
                         }
                     }
@@ -96,8 +450,23 @@ impl Action {
     }
 }
 
+/// Give a status to each target for a number of their turns
+fn add_status(
+    caster: Entity,
+    targets: &[Entity],
+    kind: StatusKind,
+    duration: u32,
+    warrior_query: &mut ActionTargetQuery,
+) {
+    for &target in targets.iter() {
+        if let Ok((_, _, _, _, _, mut status_effects, ..)) = warrior_query.get_mut(target) {
+            status_effects.add(Status::new(caster, kind, duration));
+        }
+    }
+}
+
 /// The action Area Of Effect, representing the zone where the action effects will be applied
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum ActionAoe {
     /// ☐☐☐☐☐☐ <br/>
     /// ☐☐☐☐☐☐ <br/>
@@ -135,6 +504,107 @@ pub enum ActionAoe {
     Cross { min_length: u32, max_length: u32 },
 }
 
+impl ActionAoe {
+    /// Get the positions covered by the area of effect, oriented from the caster to the target position
+    /// and clipped to the map. The area stops on the positions for which `is_blocked` is true.
+    pub fn positions(
+        &self,
+        from_position: &MapPosition,
+        to_position: &MapPosition,
+        map_width: u32,
+        map_height: u32,
+        mut is_blocked: impl FnMut(&MapPosition) -> bool,
+    ) -> Vec<MapPosition> {
+        let in_map = |(x, y): (i32, i32)| {
+            (x >= 0 && y >= 0 && x < map_width as i32 && y < map_height as i32)
+                .then(|| MapPosition::new(x as u32, y as u32))
+        };
+        let (target_x, target_y) = (to_position.x as i32, to_position.y as i32);
+
+        // Walk from the target along a step, from the min to the max offset, until blocked
+        let mut ray = |(dx, dy): (i32, i32), min: u32, max: u32| {
+            let mut positions = Vec::new();
+            for offset in 1..=max as i32 {
+                let position = match in_map((target_x + dx * offset, target_y + dy * offset)) {
+                    Some(position) => position,
+                    None => break,
+                };
+                if is_blocked(&position) {
+                    break;
+                }
+                if offset >= min as i32 {
+                    positions.push(position);
+                }
+            }
+            positions
+        };
+
+        match *self {
+            Self::Cell => vec![*to_position],
+            Self::Zone {
+                min_distance,
+                max_distance,
+            } => {
+                let candidates = to_position.get_surrounding_positions(
+                    min_distance,
+                    max_distance,
+                    map_width,
+                    map_height,
+                );
+                // Obstacles hide the positions behind them from the center of the zone
+                candidates
+                    .into_iter()
+                    .filter(|position| {
+                        to_position
+                            .line_to(position)
+                            .iter()
+                            .skip(1)
+                            .all(|position| !is_blocked(position))
+                    })
+                    .collect()
+            }
+            Self::Line {
+                distance,
+                forward_length,
+                away_length,
+            } => match from_position.direction_to(to_position) {
+                Some(direction) => {
+                    let step = direction.step();
+                    let mut positions = Vec::new();
+                    if distance == 0 {
+                        positions.push(*to_position);
+                    }
+                    let min = distance.max(1);
+                    if forward_length > 0 {
+                        let max = distance + forward_length - 1;
+                        positions.extend(ray(step, min, max));
+                    }
+                    if away_length > 0 {
+                        let max = distance + away_length - 1;
+                        positions.extend(ray((-step.0, -step.1), min, max));
+                    }
+                    positions
+                }
+                // Lines can only be oriented along the straight directions
+                None => vec![*to_position],
+            },
+            Self::Cross {
+                min_length,
+                max_length,
+            } => {
+                let mut positions = Vec::new();
+                if min_length == 0 {
+                    positions.push(*to_position);
+                }
+                for step in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    positions.extend(ray(step, min_length.max(1), max_length));
+                }
+                positions
+            }
+        }
+    }
+}
+
 impl Default for ActionAoe {
     fn default() -> Self {
         Self::Cell
@@ -142,7 +612,7 @@ impl Default for ActionAoe {
 }
 
 /// The action range represents the targetable cells from the attacker position
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum ActionRange {
     Around {
         min_distance: u32,
@@ -158,6 +628,44 @@ pub enum ActionRange {
     },
 }
 
+impl ActionRange {
+    /// Get the (min, max) distance bounds of the range
+    pub fn bounds(&self) -> (u32, u32) {
+        match *self {
+            Self::Around {
+                min_distance,
+                max_distance,
+            } => (min_distance, max_distance),
+            Self::Line {
+                min_distance,
+                max_distance,
+            } => (min_distance, max_distance),
+            Self::Diagonal {
+                min_distance,
+                max_distance,
+            } => (min_distance, max_distance),
+        }
+    }
+
+    /// Check if the target position is in range: anywhere around, on the straight lines or on the diagonals
+    pub fn can_reach(&self, from_position: &MapPosition, to_position: &MapPosition) -> bool {
+        let (min_distance, max_distance) = self.bounds();
+        let dx = (to_position.x as i32 - from_position.x as i32).abs() as u32;
+        let dy = (to_position.y as i32 - from_position.y as i32).abs() as u32;
+
+        let distance = match *self {
+            Self::Around { .. } => Some(dx + dy),
+            Self::Line { .. } => (dx == 0 || dy == 0).then(|| dx + dy),
+            // The distance of a diagonal is its number of steps
+            Self::Diagonal { .. } => (dx == dy).then(|| dx),
+        };
+
+        distance
+            .map(|distance| distance >= min_distance && distance <= max_distance)
+            .unwrap_or(false)
+    }
+}
+
 impl Default for ActionRange {
     fn default() -> Self {
         Self::Around {
@@ -168,7 +676,7 @@ impl Default for ActionRange {
 }
 
 /// An effect is an outcome of an action execution
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum ActionEffect {
     Nothing,
     Damage {
@@ -182,11 +690,22 @@ pub enum ActionEffect {
         erode: f32,
         duration: u32,
     },
+    HealOverTime {
+        amount: u32,
+        duration: u32,
+    },
     Heal {
         amount: u32,
     },
+    /// Give back the max health lost by erosion
+    RestoreErosion {
+        amount: u32,
+    },
     Shield {
         amount: u32,
+        /// Number of turns of the target before the shield expires, permanent if none
+        #[serde(default)]
+        duration: Option<u32>,
     },
     RemoveActionPoints {
         amount: u32,
@@ -200,6 +719,17 @@ pub enum ActionEffect {
     StealMovementPoints {
         amount: u32,
     },
+    ActionPointsDebuff {
+        amount: u32,
+        duration: u32,
+    },
+    MovementPointsDebuff {
+        amount: u32,
+        duration: u32,
+    },
+    Stun {
+        duration: u32,
+    },
     TeleportSelf,
     TeleportSwitch,
     PushLinear {
@@ -215,3 +745,372 @@ impl Default for ActionEffect {
         Self::Nothing
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::app::Events;
+    use bevy::ecs::system::SystemState;
+
+    const CASTER_POSITION: MapPosition = MapPosition { x: 0, y: 0 };
+    const TARGET_POSITION: MapPosition = MapPosition { x: 1, y: 0 };
+
+    fn attribute<T: AttributeValue + Copy + Clone + Default>(value: T, max: T) -> Attribute<T> {
+        let mut attribute = Attribute::default();
+        attribute.set_max(max);
+        attribute.set_value(value);
+        attribute
+    }
+
+    fn spawn_warrior(world: &mut World, position: MapPosition) -> Entity {
+        world
+            .spawn()
+            .insert_bundle((
+                Warrior,
+                position,
+                attribute(Health(500), Health(500)),
+                Erosion::default(),
+                attribute(Shield(0), Shield(1000)),
+                StatusEffects::default(),
+                attribute(ActionPoints(6), ActionPoints(12)),
+                attribute(MovementPoints(3), MovementPoints(6)),
+            ))
+            .id()
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(Events::<CombatEvent>::default());
+        world
+    }
+
+    /// A world with a caster and a target standing next to it
+    fn setup() -> (World, Entity, Entity) {
+        let mut world = world();
+        let caster = spawn_warrior(&mut world, CASTER_POSITION);
+        let target = spawn_warrior(&mut world, TARGET_POSITION);
+        (world, caster, target)
+    }
+
+    /// An action hitting the targeted cell, anywhere on the map
+    fn action(effect: ActionEffect) -> Action {
+        Action {
+            name: "test".to_string(),
+            icon_key: "test".to_string(),
+            animation_key: "test".to_string(),
+            cost: ActionPoints(0),
+            aoe: ActionAoe::Cell,
+            range: ActionRange::Around {
+                min_distance: 0,
+                max_distance: 20,
+            },
+            effects: vec![effect],
+            aoe_stops_on_obstacles: false,
+            ignore_line_of_sight: false,
+            impact_frame: 0,
+        }
+    }
+
+    /// Execute the effect from the caster on a 10x10 map without obstacles
+    fn execute(world: &mut World, effect: ActionEffect, caster: Entity, to: MapPosition) {
+        let nav_grid = NavGrid::new(10, 10);
+        let mut rng = GameRng::new(0);
+        let mut system_state: SystemState<(
+            ActionTargetQuery<'static, 'static>,
+            EventWriter<'static, 'static, CombatEvent>,
+        )> = SystemState::new(world);
+
+        let (mut warrior_query, mut ev_combat) = system_state.get_mut(world);
+        action(effect).execute(
+            caster,
+            &to,
+            &nav_grid,
+            &mut warrior_query,
+            &mut rng,
+            &mut ev_combat,
+        );
+        system_state.apply(world);
+    }
+
+    /// Execute the effect from the caster on the target
+    fn cast(effect: ActionEffect) -> (World, Entity, Entity) {
+        let (mut world, caster, target) = setup();
+        execute(&mut world, effect, caster, TARGET_POSITION);
+        (world, caster, target)
+    }
+
+    fn health(world: &World, entity: Entity) -> Attribute<Health> {
+        *world.get::<Attribute<Health>>(entity).unwrap()
+    }
+
+    fn position(world: &World, entity: Entity) -> MapPosition {
+        *world.get::<MapPosition>(entity).unwrap()
+    }
+
+    fn points(world: &World, entity: Entity) -> (u32, u32) {
+        let action_points = world.get::<Attribute<ActionPoints>>(entity).unwrap();
+        let movement_points = world.get::<Attribute<MovementPoints>>(entity).unwrap();
+        (action_points.value(), movement_points.value())
+    }
+
+    fn statuses(world: &World, entity: Entity) -> Vec<Status> {
+        world
+            .get::<StatusEffects>(entity)
+            .unwrap()
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn nothing_changes_nothing() {
+        let (world, _, target) = cast(ActionEffect::Nothing);
+
+        assert_eq!(health(&world, target).value(), 500);
+        assert_eq!(position(&world, target), TARGET_POSITION);
+        assert!(statuses(&world, target).is_empty());
+    }
+
+    #[test]
+    fn damage_drops_and_erodes_health() {
+        // (crit_chance, crit_mult, erode, expected health, expected max health)
+        let cases = [(0.0, 2.0, 0.1, 400, 490), (1.0, 1.5, 0.0, 350, 500)];
+
+        for (crit_chance, crit_mult, erode, expected_health, expected_max) in cases {
+            let (world, caster, target) = cast(ActionEffect::Damage {
+                amount: 100,
+                erode,
+                crit_mult,
+                crit_chance,
+            });
+
+            assert_eq!(health(&world, target).value(), expected_health);
+            assert_eq!(health(&world, target).max(), expected_max);
+            assert_eq!(world.get::<Erosion>(target).unwrap().0, 500 - expected_max);
+            assert_eq!(health(&world, caster).value(), 500);
+        }
+    }
+
+    #[test]
+    fn heal_rises_health_up_to_max() {
+        let (mut world, caster, target) = setup();
+        world
+            .get_mut::<Attribute<Health>>(target)
+            .unwrap()
+            .set_value(Health(450));
+
+        execute(
+            &mut world,
+            ActionEffect::Heal { amount: 100 },
+            caster,
+            TARGET_POSITION,
+        );
+
+        assert_eq!(health(&world, target).value(), 500);
+    }
+
+    #[test]
+    fn restore_erosion_gives_back_max_health() {
+        let (mut world, caster, target) = setup();
+        world
+            .get_mut::<Attribute<Health>>(target)
+            .unwrap()
+            .set_max(Health(440));
+        world.get_mut::<Erosion>(target).unwrap().0 = 60;
+
+        execute(
+            &mut world,
+            ActionEffect::RestoreErosion { amount: 100 },
+            caster,
+            TARGET_POSITION,
+        );
+
+        assert_eq!(health(&world, target).max(), 500);
+        assert_eq!(health(&world, target).value(), 440);
+        assert_eq!(world.get::<Erosion>(target).unwrap().0, 0);
+    }
+
+    #[test]
+    fn shield_rises_shield_with_its_status() {
+        let (world, _, target) = cast(ActionEffect::Shield {
+            amount: 120,
+            duration: Some(2),
+        });
+
+        assert_eq!(world.get::<Attribute<Shield>>(target).unwrap().value(), 120);
+        let statuses = statuses(&world, target);
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].kind, StatusKind::Shield { amount: 120 });
+    }
+
+    #[test]
+    fn status_effects_add_a_status_to_the_target_only() {
+        // (effect, expected status kind, expected turns)
+        let cases = [
+            (
+                ActionEffect::DamageOverTime {
+                    amount: 30,
+                    erode: 0.1,
+                    duration: 2,
+                },
+                StatusKind::DamageOverTime {
+                    amount: 30,
+                    erode: 0.1,
+                },
+                2,
+            ),
+            (
+                ActionEffect::HealOverTime {
+                    amount: 40,
+                    duration: 3,
+                },
+                StatusKind::HealOverTime { amount: 40 },
+                3,
+            ),
+            (
+                ActionEffect::ActionPointsDebuff {
+                    amount: 1,
+                    duration: 2,
+                },
+                StatusKind::ActionPointsDebuff { amount: 1 },
+                2,
+            ),
+            (
+                ActionEffect::MovementPointsDebuff {
+                    amount: 1,
+                    duration: 2,
+                },
+                StatusKind::MovementPointsDebuff { amount: 1 },
+                2,
+            ),
+            (ActionEffect::Stun { duration: 1 }, StatusKind::Stun, 1),
+        ];
+
+        for (effect, kind, turns) in cases {
+            let (world, caster, target) = cast(effect);
+            assert!(statuses(&world, caster).is_empty());
+
+            let statuses = statuses(&world, target);
+            assert_eq!(statuses.len(), 1);
+            assert_eq!(statuses[0].caster, caster);
+            assert_eq!(statuses[0].kind, kind);
+            assert_eq!(statuses[0].turns, turns);
+
+            // The status applies on the next turns, not right away
+            assert_eq!(health(&world, target).value(), 500);
+            assert_eq!(points(&world, target), (6, 3));
+        }
+    }
+
+    #[test]
+    fn points_removal_credits_the_caster_only_when_stolen() {
+        // (effect, expected target points, expected caster points), as (action, movement)
+        let cases = [
+            (
+                ActionEffect::RemoveActionPoints { amount: 2 },
+                (4, 3),
+                (6, 3),
+            ),
+            (
+                ActionEffect::StealActionPoints { amount: 2 },
+                (4, 3),
+                (8, 3),
+            ),
+            (
+                ActionEffect::RemoveMovementPoints { amount: 2 },
+                (6, 1),
+                (6, 3),
+            ),
+            // Only the movement points the target had can be stolen
+            (
+                ActionEffect::StealMovementPoints { amount: 5 },
+                (6, 0),
+                (6, 6),
+            ),
+        ];
+
+        for (effect, target_points, caster_points) in cases {
+            let (world, caster, target) = cast(effect);
+
+            assert_eq!(points(&world, target), target_points);
+            assert_eq!(points(&world, caster), caster_points);
+        }
+    }
+
+    #[test]
+    fn teleport_self_moves_the_caster_on_a_free_position() {
+        let (mut world, caster, target) = setup();
+
+        execute(
+            &mut world,
+            ActionEffect::TeleportSelf,
+            caster,
+            TARGET_POSITION,
+        );
+        assert_eq!(position(&world, caster), CASTER_POSITION);
+
+        execute(
+            &mut world,
+            ActionEffect::TeleportSelf,
+            caster,
+            MapPosition::new(5, 5),
+        );
+        assert_eq!(position(&world, caster), MapPosition::new(5, 5));
+        assert_eq!(position(&world, target), TARGET_POSITION);
+    }
+
+    #[test]
+    fn teleport_switch_swaps_the_caster_and_the_target() {
+        let (world, caster, target) = cast(ActionEffect::TeleportSwitch);
+
+        assert_eq!(position(&world, caster), TARGET_POSITION);
+        assert_eq!(position(&world, target), CASTER_POSITION);
+    }
+
+    #[test]
+    fn push_moves_the_target_and_hurts_on_collisions() {
+        let linear = ActionEffect::PushLinear { distance: 3 };
+        let diagonal = ActionEffect::PushDiagonal { distance: 3 };
+        let collision = |cells: u32| 500 - cells * PUSH_COLLISION_DAMAGES;
+
+        // (effect, caster, target, blocking warrior, expected position, expected health)
+        let cases = [
+            (linear.clone(), (0, 0), (1, 0), None, (4, 0), 500),
+            (
+                linear.clone(),
+                (0, 0),
+                (1, 0),
+                Some((3, 0)),
+                (2, 0),
+                collision(2),
+            ),
+            (
+                linear.clone(),
+                (0, 0),
+                (1, 0),
+                Some((2, 0)),
+                (1, 0),
+                collision(3),
+            ),
+            (linear.clone(), (0, 2), (0, 1), None, (0, 0), collision(2)),
+            (diagonal.clone(), (0, 0), (1, 1), None, (4, 4), 500),
+            (diagonal.clone(), (6, 6), (7, 7), None, (9, 9), collision(1)),
+        ];
+
+        for (effect, caster, target, blocker, expected_position, expected_health) in cases {
+            let mut world = world();
+            let caster = spawn_warrior(&mut world, MapPosition::new(caster.0, caster.1));
+            let target_position = MapPosition::new(target.0, target.1);
+            let target = spawn_warrior(&mut world, target_position);
+            if let Some((x, y)) = blocker {
+                spawn_warrior(&mut world, MapPosition::new(x, y));
+            }
+
+            execute(&mut world, effect, caster, target_position);
+
+            let (x, y) = expected_position;
+            assert_eq!(position(&world, target), MapPosition::new(x, y));
+            assert_eq!(health(&world, target).value(), expected_health);
+        }
+    }
+}
@@ -5,13 +5,15 @@ use bevy::utils::HashMap;
 use bevy_asset_loader::AssetCollection;
 use serde::{Deserialize, Serialize};
 
-use super::{Action, ActionPoints, Attribute, Health, MovementPoints, Shield};
+use super::{
+    Action, ActionPoints, AnimationClip, Attribute, Health, Initiative, MovementPoints, Shield,
+};
 
 // TODO find a way to load a folder into as HashMap<String, Handle<..>>
 #[derive(AssetCollection, Reflect)]
 pub struct AnimationCollection {
-    #[asset(key = "animations.ella")]
-    pub ella: Handle<TextureAtlas>,
+    #[asset(key = "animations.knight")]
+    pub knight: Handle<TextureAtlas>,
 }
 
 impl AnimationCollection {
@@ -157,13 +159,19 @@ pub struct WarriorAsset {
     pub shield: Attribute<Shield>,
     pub action_points: Attribute<ActionPoints>,
     pub movement_points: Attribute<MovementPoints>,
+    pub initiative: Attribute<Initiative>,
     pub actions: Vec<Action>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct WarriorAssetRender {
     pub atlas_texture: String,
-    pub animations: HashMap<String, (usize, usize)>,
+
+    /// The sprite sheet only draws warriors facing east, they are mirrored to face west
+    #[serde(default)]
+    pub mirror_west: bool,
+
+    pub animations: HashMap<String, AnimationClip>,
 }
 
 pub struct WarriorAssetLoader;
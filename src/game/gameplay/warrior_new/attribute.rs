@@ -2,7 +2,8 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// An attribute is a utility wrapper type for any warrior attribute (health, action points, armor, etc.)
-#[derive(Debug, Copy, Clone, Default, Component, Deserialize, Serialize)]
+#[derive(Reflect, Debug, Copy, Clone, Default, Component, Deserialize, Serialize)]
+#[reflect(Component)]
 pub struct Attribute<T: AttributeValue + Copy + Clone + Default> {
     value: T,
     min: T,
@@ -69,30 +70,40 @@ impl<T: AttributeValue + Copy + Clone + Default> Attribute<T> {
     pub fn rise_max(&mut self) -> u32 {
         self.rise(self.max())
     }
+
+    /// Can the value be dropped by the amount without going below min
+    pub fn can_drop(self, amount: u32) -> bool {
+        self.value()
+            .checked_sub(amount)
+            .map(|remaining| remaining >= self.min())
+            .unwrap_or(false)
+    }
+
+    pub fn as_text(self) -> String {
+        format!("{} / {}", self.value(), self.max())
+    }
 }
 
 impl Attribute<Health> {
-    pub fn erode(&mut self, amount: u32, erode: f32) {
-        let erosion = (amount as f32 - erode).round() as u32;
-        let new_max = self
-            .max
-            .value()
-            .checked_sub(erosion)
-            .filter(|&v| v >= self.min())
-            .unwrap_or(self.min());
+    /// Permanently lower the max health by a percentage (from 0 to 1) of the damages, returning the eroded amount
+    pub fn erode(&mut self, amount: u32, erode: f32) -> u32 {
+        let erosion = (amount as f32 * erode.clamp(0.0, 1.0)).round() as u32;
+        let old_max = self.max();
+
+        self.set_max(Health(old_max.saturating_sub(erosion)));
 
-        self.set_max(Health(new_max));
+        old_max - self.max()
     }
 }
 
 /// A way to interact with attributes NewTypes (maybe their is a better way ?)
-pub trait AttributeValue {
+pub trait AttributeValue: Reflect {
     fn value(&self) -> u32;
     fn set_value(&mut self, value: u32);
 }
 
 /// NewType representing a Warrior's action points quantity
-#[derive(Debug, Copy, Clone, Default, Deserialize, Serialize)]
+#[derive(Reflect, Debug, Copy, Clone, Default, Deserialize, Serialize)]
 pub struct ActionPoints(pub u32);
 
 impl AttributeValue for ActionPoints {
@@ -105,7 +116,7 @@ impl AttributeValue for ActionPoints {
 }
 
 /// NewType representing a Warrior's movement points quantity
-#[derive(Debug, Copy, Clone, Default, Deserialize, Serialize)]
+#[derive(Reflect, Debug, Copy, Clone, Default, Deserialize, Serialize)]
 pub struct MovementPoints(pub u32);
 
 impl AttributeValue for MovementPoints {
@@ -118,7 +129,7 @@ impl AttributeValue for MovementPoints {
 }
 
 /// NewType representing a Warrior's health quantity
-#[derive(Debug, Copy, Clone, Default, Deserialize, Serialize)]
+#[derive(Reflect, Debug, Copy, Clone, Default, Deserialize, Serialize)]
 pub struct Health(pub u32);
 
 impl AttributeValue for Health {
@@ -131,7 +142,7 @@ impl AttributeValue for Health {
 }
 
 /// NewType representing a Warrior's shield quantity
-#[derive(Debug, Copy, Clone, Default, Deserialize, Serialize)]
+#[derive(Reflect, Debug, Copy, Clone, Default, Deserialize, Serialize)]
 pub struct Shield(pub u32);
 
 impl AttributeValue for Shield {
@@ -142,3 +153,85 @@ impl AttributeValue for Shield {
         self.0 = value;
     }
 }
+
+/// NewType representing a Warrior's initiative, the higher it is the sooner the warrior plays
+#[derive(Reflect, Debug, Copy, Clone, Default, Deserialize, Serialize)]
+pub struct Initiative(pub u32);
+
+impl AttributeValue for Initiative {
+    fn value(&self) -> u32 {
+        self.0
+    }
+    fn set_value(&mut self, value: u32) {
+        self.0 = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(value: u32, min: u32, max: u32) -> Attribute<Health> {
+        let mut health = Attribute::default();
+        health.set_max(Health(max));
+        health.set_min(Health(min));
+        health.set_value(Health(value));
+        health
+    }
+
+    #[test]
+    fn set_max_below_min_is_clamped_to_min() {
+        let mut attribute = health(50, 10, 100);
+        attribute.set_max(Health(5));
+
+        assert_eq!(attribute.max(), 10);
+        assert_eq!(attribute.value(), 10);
+    }
+
+    #[test]
+    fn set_max_below_value_lowers_value() {
+        let mut attribute = health(80, 0, 100);
+        attribute.set_max(Health(50));
+
+        assert_eq!(attribute.max(), 50);
+        assert_eq!(attribute.value(), 50);
+    }
+
+    #[test]
+    fn set_max_above_value_keeps_value() {
+        let mut attribute = health(80, 0, 100);
+        attribute.set_max(Health(200));
+
+        assert_eq!(attribute.max(), 200);
+        assert_eq!(attribute.value(), 80);
+    }
+
+    #[test]
+    fn erode_lowers_max_by_a_percentage() {
+        let mut attribute = health(400, 0, 500);
+
+        assert_eq!(attribute.erode(100, 0.25), 25);
+        assert_eq!(attribute.max(), 475);
+        assert_eq!(attribute.value(), 400);
+    }
+
+    #[test]
+    fn erode_is_clamped_between_0_and_1() {
+        let mut attribute = health(400, 0, 500);
+
+        assert_eq!(attribute.erode(100, 1.5), 100);
+        assert_eq!(attribute.max(), 400);
+
+        assert_eq!(attribute.erode(100, -0.5), 0);
+        assert_eq!(attribute.max(), 400);
+    }
+
+    #[test]
+    fn erode_stops_at_min() {
+        let mut attribute = health(20, 10, 30);
+
+        assert_eq!(attribute.erode(100, 1.0), 20);
+        assert_eq!(attribute.max(), 10);
+        assert_eq!(attribute.value(), 10);
+    }
+}
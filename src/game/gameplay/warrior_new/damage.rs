@@ -0,0 +1,97 @@
+use super::{Attribute, Health, Shield, StatusEffects};
+use bevy::prelude::*;
+
+/// Max health a warrior lost by erosion for the rest of the fight
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct Erosion(pub u32);
+
+/// Hurt a warrior, its shield absorbs the damages first and the overflow drops and erodes its health.
+/// Returns the health lost and the damages absorbed by the shield.
+pub fn inflict_damage(
+    amount: u32,
+    erode: f32,
+    health: &mut Attribute<Health>,
+    erosion: &mut Erosion,
+    shield: &mut Attribute<Shield>,
+    status_effects: &mut StatusEffects,
+) -> (u32, u32) {
+    let overflow = shield.drop(amount);
+    let absorbed = amount - overflow;
+    status_effects.absorb(absorbed);
+
+    let previous_health = health.value();
+    health.drop(overflow);
+    erosion.0 += health.erode(previous_health - health.value(), erode);
+
+    (previous_health - health.value(), absorbed)
+}
+
+/// Give back max health lost by erosion, returning the restored amount
+pub fn restore_eroded_health(
+    amount: u32,
+    health: &mut Attribute<Health>,
+    erosion: &mut Erosion,
+) -> u32 {
+    let restored = amount.min(erosion.0);
+    erosion.0 -= restored;
+    health.set_max(Health(health.max() + restored));
+
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(value: u32, max: u32) -> Attribute<Health> {
+        let mut health = Attribute::default();
+        health.set_max(Health(max));
+        health.set_value(Health(value));
+        health
+    }
+
+    #[test]
+    fn restore_eroded_health_is_capped_by_erosion() {
+        let mut health = health(300, 470);
+        let mut erosion = Erosion(30);
+
+        assert_eq!(restore_eroded_health(100, &mut health, &mut erosion), 30);
+        assert_eq!(erosion.0, 0);
+        assert_eq!(health.max(), 500);
+        assert_eq!(health.value(), 300);
+    }
+
+    #[test]
+    fn restore_eroded_health_keeps_the_remaining_erosion() {
+        let mut health = health(300, 400);
+        let mut erosion = Erosion(100);
+
+        assert_eq!(restore_eroded_health(40, &mut health, &mut erosion), 40);
+        assert_eq!(erosion.0, 60);
+        assert_eq!(health.max(), 440);
+    }
+
+    #[test]
+    fn inflict_damage_erodes_the_health_lost_only() {
+        let mut health = health(500, 500);
+        let mut erosion = Erosion::default();
+        let mut shield = Attribute::<Shield>::default();
+        shield.set_max(Shield(100));
+        shield.set_value(Shield(60));
+        let mut status_effects = StatusEffects::default();
+
+        let (lost, absorbed) = inflict_damage(
+            160,
+            0.5,
+            &mut health,
+            &mut erosion,
+            &mut shield,
+            &mut status_effects,
+        );
+
+        assert_eq!((lost, absorbed), (100, 60));
+        assert_eq!(health.value(), 400);
+        assert_eq!(erosion.0, 50);
+        assert_eq!(health.max(), 450);
+    }
+}
@@ -1,18 +1,26 @@
 mod action;
 mod asset;
 mod attribute;
+mod damage;
 mod render;
+mod status;
 
 use bevy::prelude::*;
 
 pub use action::*;
 pub use asset::*;
 pub use attribute::*;
+pub use damage::*;
 pub use render::*;
+pub use status::*;
 
 #[derive(Default, Component)]
 pub struct Warrior;
 
+/// A warrior playing its death animation, no longer part of the fight
+#[derive(Default, Component)]
+pub struct Dead;
+
 #[derive(Default, Bundle)]
 pub struct WarriorBundle {
     // Tags
@@ -24,15 +32,53 @@ pub struct WarriorBundle {
     // Gameplay
     position: super::MapPosition,
     health: Attribute<Health>,
+    erosion: Erosion,
     shield: Attribute<Shield>,
+    status_effects: StatusEffects,
     action_points: Attribute<ActionPoints>,
     movement_points: Attribute<MovementPoints>,
+    initiative: Attribute<Initiative>,
     actions: Actions,
 
     // TODO add animation collection ? How to load it ?
     // Redering
     #[bundle]
     sprite: SpriteSheetBundle,
-    animation_timer: AnimationTimer,
-    animation: SelectedAnimation,
+    animation: AnimationController,
+    facing: Facing,
+}
+
+impl WarriorBundle {
+    pub fn new(
+        name: String,
+        position: super::MapPosition,
+        asset: &WarriorAsset,
+        facing: super::MapPositionDirection,
+        texture_atlas_handle: &Handle<TextureAtlas>,
+        atlas_len: usize,
+    ) -> Self {
+        WarriorBundle {
+            name: Name::new(name),
+            position,
+            health: asset.health,
+            shield: asset.shield,
+            action_points: asset.action_points,
+            movement_points: asset.movement_points,
+            initiative: asset.initiative,
+            actions: Actions::new(asset.actions.clone()),
+            sprite: SpriteSheetBundle {
+                texture_atlas: texture_atlas_handle.clone(),
+                transform: Transform::from_translation(Vec3::new(0.0, 0.0, 2.0))
+                    .with_scale(Vec3::new(1.0, 1.2, 1.0)),
+                ..Default::default()
+            },
+            animation: AnimationController::new(
+                asset.render.animations.clone(),
+                asset.render.mirror_west,
+                atlas_len,
+            ),
+            facing: Facing(facing),
+            ..Default::default()
+        }
+    }
 }
@@ -1,51 +1,244 @@
-use super::super::{Map, MapPosition};
-use super::Warrior;
+use super::super::{CombatEvent, Map, MapPosition, MapPositionDirection, PendingAction};
+use super::{Actions, Warrior};
 use bevy::prelude::*;
 use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
 
-#[derive(Reflect, Component, Default)]
-#[reflect(Component)]
-pub struct AnimationTimer(pub Timer);
+/// Time for a warrior to walk from a cell to the next one
+const MOVEMENT_STEP_SECONDS: f32 = 0.2;
+
+/// Sent when a warrior played an animation until its last frame, looping animations never finish
+pub struct AnimationFinished {
+    pub entity: Entity,
+    pub key: String,
+}
+
+/// The direction a warrior looks at
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Facing(pub MapPositionDirection);
+
+impl Default for Facing {
+    fn default() -> Self {
+        Self(MapPositionDirection::SudEst)
+    }
+}
+
+/// An animation, with its first and last frames in the TextureAtlas (eg. idle 0-15)
+#[derive(Reflect, Debug, Default, Clone, Deserialize, Serialize)]
+pub struct AnimationClip {
+    pub frames: (usize, usize),
+
+    /// Seconds each frame is shown
+    #[serde(default = "default_frame_duration")]
+    pub frame_duration: f32,
+
+    /// Looping animations play until another one is selected, the others go back to idle once played
+    #[serde(default)]
+    pub looping: bool,
+}
 
+fn default_frame_duration() -> f32 {
+    0.15
+}
+
+/// Play the animations of a warrior, one at a time
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
-pub struct SelectedAnimation {
-    /// Currently selected animation
+pub struct AnimationController {
+    /// Currently played animation
     current_key: String,
 
-    /// Bound of each available animation (eg. idle 0-15, walk 16-45, attack 46-82)
-    animations: HashMap<String, (usize, usize)>,
+    /// Frame of the current animation, from its first one
+    frame: usize,
+
+    /// Seconds the current frame has been shown
+    elapsed: f32,
+
+    /// Each available animation
+    animations: HashMap<String, AnimationClip>,
+
+    /// The frames only face east, they are mirrored to face west
+    mirror_west: bool,
+
+    /// Number of frames in the TextureAtlas, the clips going past it are never played
+    atlas_len: usize,
 }
 
-impl SelectedAnimation {
-    /// Get the next sprite index in the TextureAtlas based on the current index and the selected animation
-    fn next(&self, index: usize) -> usize {
-        let (min, max) = self.animations.get(&self.current_key).unwrap();
-        index % max - min
+impl AnimationController {
+    pub fn new(
+        animations: HashMap<String, AnimationClip>,
+        mirror_west: bool,
+        atlas_len: usize,
+    ) -> Self {
+        Self {
+            current_key: String::from("idle"),
+            animations,
+            mirror_west,
+            atlas_len,
+            ..Default::default()
+        }
+    }
+
+    /// Switch to another animation if the warrior has it, an already playing animation goes on
+    pub fn select(&mut self, key: &str) {
+        if self.current_key != key {
+            self.play(key);
+        }
+    }
+
+    /// Play an animation from its first frame if the warrior has it within its TextureAtlas,
+    /// returning if it is played
+    pub fn play(&mut self, key: &str) -> bool {
+        let fits_atlas = self
+            .animations
+            .get(key)
+            .map_or(false, |clip| clip.frames.1 < self.atlas_len);
+        if !fits_atlas {
+            return false;
+        }
+
+        self.current_key = key.to_string();
+        self.frame = 0;
+        self.elapsed = 0.0;
+        true
+    }
+
+    /// Check if the given animation is the current one
+    pub fn is_playing(&self, key: &str) -> bool {
+        self.current_key == key
+    }
+
+    /// Get the frame of the current animation, from its first one
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Get the sprite index in the TextureAtlas of the current frame
+    fn sprite_index(&self) -> Option<usize> {
+        let (min, max) = self.animations.get(&self.current_key)?.frames;
+        Some((min + self.frame).min(max)).filter(|&index| index < self.atlas_len)
+    }
+
+    /// Move the animation forward in time, returning the key of the animation if it just finished
+    fn advance(&mut self, delta: f32) -> Option<String> {
+        let clip = self.animations.get(&self.current_key)?;
+        let (min, max) = clip.frames;
+        let frame_count = max.saturating_sub(min) + 1;
+        let frame_duration = clip.frame_duration.max(0.01);
+        let looping = clip.looping;
+
+        self.elapsed += delta;
+        while self.elapsed >= frame_duration {
+            self.elapsed -= frame_duration;
+            self.frame += 1;
+
+            if self.frame >= frame_count {
+                if looping {
+                    self.frame = 0;
+                } else {
+                    let finished = self.current_key.clone();
+                    self.play("idle");
+                    return Some(finished);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Are the frames mirrored to face the given direction
+    fn is_mirrored(&self, direction: MapPositionDirection) -> bool {
+        self.mirror_west
+            && matches!(
+                direction,
+                MapPositionDirection::NordWest | MapPositionDirection::SudWest
+            )
     }
 }
 
-/// Animate the sprite based on the AnimationTimer
+/// Animate the sprites facing the warriors direction, with the duration of each animation frames
 pub fn animate_warrior_sprite(
     time: Res<Time>,
+    mut ev_finished: EventWriter<AnimationFinished>,
     mut query: Query<(
-        &mut AnimationTimer,
+        Entity,
+        &mut AnimationController,
         &mut TextureAtlasSprite,
-        &SelectedAnimation,
+        &Facing,
     )>,
 ) {
-    for (mut timer, mut sprite, animations) in query.iter_mut() {
-        timer.0.tick(time.delta());
-        if timer.0.just_finished() {
-            sprite.index = animations.next(sprite.index);
+    for (entity, mut animation, mut sprite, facing) in query.iter_mut() {
+        if let Some(key) = animation.advance(time.delta_seconds()) {
+            ev_finished.send(AnimationFinished { entity, key });
         }
+
+        if let Some(index) = animation.sprite_index() {
+            sprite.index = index;
+        }
+        sprite.flip_x = animation.is_mirrored(facing.0);
     }
 }
 
-/// Update the warrior's Transform based on it's MapPosition
+/// Play the action animation of the casters and the hit animation of the hurt warriors
+pub fn play_combat_animations(
+    mut ev_combat: EventReader<CombatEvent>,
+    mut warrior_query: Query<
+        (&Actions, &mut AnimationController, Option<&PendingAction>),
+        With<Warrior>,
+    >,
+) {
+    for ev in ev_combat.iter() {
+        match ev {
+            CombatEvent::ActionUsed { caster, action, .. } => {
+                if let Ok((actions, mut animation, _)) = warrior_query.get_mut(*caster) {
+                    if let Some(action) = actions.iter().find(|a| a.name.eq(action)) {
+                        animation.play(action.animation_key.as_str());
+                    }
+                }
+            }
+            CombatEvent::Damage { target, .. } => {
+                if let Ok((_, mut animation, pending)) = warrior_query.get_mut(*target) {
+                    // A caster hit by its own action plays it until the end
+                    let is_casting = pending.map_or(false, |pending| {
+                        animation.is_playing(pending.animation_key())
+                    });
+                    if !is_casting {
+                        animation.play("attacked");
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Face the target of the actions
+pub fn face_action_target(
+    mut ev_combat: EventReader<CombatEvent>,
+    mut warrior_query: Query<(&MapPosition, &mut Facing), With<Warrior>>,
+) {
+    for ev in ev_combat.iter() {
+        if let CombatEvent::ActionUsed { caster, to, .. } = ev {
+            if let Ok((position, mut facing)) = warrior_query.get_mut(*caster) {
+                if let Some(direction) = position.facing_to(to) {
+                    facing.0 = direction;
+                }
+            }
+        }
+    }
+}
+
+/// Update the warrior's Transform based on it's MapPosition, unless it is walking there
 pub fn update_warrior_world_position(
     map_query: Query<&Map>,
-    mut warrior_query: Query<(&mut Transform, &MapPosition), (With<Warrior>, Changed<MapPosition>)>,
+    mut warrior_query: Query<
+        (&mut Transform, &MapPosition),
+        (
+            With<Warrior>,
+            Without<WarriorMovement>,
+            Changed<MapPosition>,
+        ),
+    >,
 ) {
     if map_query.is_empty() {
         return;
@@ -71,3 +264,127 @@ pub fn update_warrior_world_position(
         );
     }
 }
+
+/// A warrior walking along a path, its MapPosition is already the end of the path
+#[derive(Component)]
+pub struct WarriorMovement {
+    path: Vec<MapPosition>,
+    step: usize,
+    progress: f32,
+}
+
+impl WarriorMovement {
+    pub fn new(path: Vec<MapPosition>) -> Self {
+        Self {
+            path,
+            step: 0,
+            progress: 0.0,
+        }
+    }
+}
+
+/// Walk the warriors along their path tile by tile, facing the direction of each step
+pub fn animate_warrior_movement(
+    mut commands: Commands,
+    time: Res<Time>,
+    map_query: Query<&Map>,
+    mut warrior_query: Query<
+        (
+            Entity,
+            &mut WarriorMovement,
+            &mut Transform,
+            &mut AnimationController,
+            &mut Facing,
+        ),
+        With<Warrior>,
+    >,
+) {
+    if map_query.is_empty() {
+        return;
+    }
+
+    let map = map_query.single();
+    let to_xyz = |position: &MapPosition| {
+        position.to_xyz(
+            map.obstacle_layer,
+            map.width,
+            map.height,
+            map.tile_width as f32,
+            map.tile_height as f32,
+        )
+    };
+
+    for (entity, mut movement, mut transform, mut animation, mut facing) in warrior_query.iter_mut()
+    {
+        movement.progress += time.delta_seconds() / MOVEMENT_STEP_SECONDS;
+        while movement.progress >= 1.0 && movement.step + 1 < movement.path.len() {
+            movement.progress -= 1.0;
+            movement.step += 1;
+        }
+
+        let step = movement.step;
+        match (movement.path.get(step), movement.path.get(step + 1)) {
+            (Some(from), Some(to)) => {
+                transform.translation = to_xyz(from).lerp(to_xyz(to), movement.progress);
+                animation.select("moving");
+
+                if let Some(direction) = from.direction_to(to) {
+                    facing.0 = direction;
+                }
+            }
+            _ => {
+                if let Some(destination) = movement.path.last() {
+                    transform.translation = to_xyz(destination);
+                }
+                animation.select("idle");
+                commands.entity(entity).remove::<WarriorMovement>();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(frames: (usize, usize), looping: bool) -> AnimationClip {
+        AnimationClip {
+            frames,
+            frame_duration: 0.1,
+            looping,
+        }
+    }
+
+    /// A warrior with an idle clip, and a moving clip past its 10 frames TextureAtlas
+    fn controller() -> AnimationController {
+        let mut animations = HashMap::default();
+        animations.insert(String::from("idle"), clip((0, 3), true));
+        animations.insert(String::from("attacked"), clip((4, 5), false));
+        animations.insert(String::from("moving"), clip((10, 15), true));
+        AnimationController::new(animations, true, 10)
+    }
+
+    #[test]
+    fn clips_past_the_atlas_are_never_played() {
+        let mut animation = controller();
+
+        assert!(!animation.play("moving"));
+        animation.select("moving");
+
+        assert!(animation.is_playing("idle"));
+        assert_eq!(animation.sprite_index(), Some(0));
+    }
+
+    #[test]
+    fn one_shot_clips_go_back_to_idle() {
+        let mut animation = controller();
+        assert!(animation.play("attacked"));
+        assert_eq!(animation.sprite_index(), Some(4));
+
+        assert_eq!(animation.advance(0.1), None);
+        assert_eq!(animation.sprite_index(), Some(5));
+
+        assert_eq!(animation.advance(0.1), Some(String::from("attacked")));
+        assert!(animation.is_playing("idle"));
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+/// A timed effect on a warrior
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusKind {
+    /// Hurt the warrior at the start of its turns
+    DamageOverTime { amount: u32, erode: f32 },
+    /// Heal the warrior at the start of its turns
+    HealOverTime { amount: u32 },
+    /// Remove action points at the start of its turns
+    ActionPointsDebuff { amount: u32 },
+    /// Remove movement points at the start of its turns
+    MovementPointsDebuff { amount: u32 },
+    /// Shield points, removed when the status expires
+    Shield { amount: u32 },
+    /// The warrior skips its turns
+    Stun,
+}
+
+/// What happens when a warrior gets a status it already has from the same caster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusStacking {
+    /// Both statuses apply
+    Stack,
+    /// The previous status is replaced, with the longest duration
+    Refresh,
+}
+
+impl StatusKind {
+    pub fn stacking(&self) -> StatusStacking {
+        match self {
+            Self::DamageOverTime { .. } | Self::HealOverTime { .. } | Self::Shield { .. } => {
+                StatusStacking::Stack
+            }
+            Self::ActionPointsDebuff { .. } | Self::MovementPointsDebuff { .. } | Self::Stun => {
+                StatusStacking::Refresh
+            }
+        }
+    }
+
+    /// Check if both statuses are the same kind of effect, whatever their amounts
+    pub fn is_same_kind(&self, other: &StatusKind) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Status {
+    /// The warrior who applied the status
+    pub caster: Entity,
+    pub kind: StatusKind,
+    /// Remaining turns of the warrior before the status expires
+    pub turns: u32,
+    /// The warrior started a turn since it got the status, the turn it got it in doesn't count
+    pub started: bool,
+}
+
+impl Status {
+    pub fn new(caster: Entity, kind: StatusKind, turns: u32) -> Self {
+        Self {
+            caster,
+            kind,
+            turns,
+            started: false,
+        }
+    }
+}
+
+/// The timed effects on a warrior, counted down at the end of each of its turns
+#[derive(Debug, Default, Component)]
+pub struct StatusEffects(Vec<Status>);
+
+impl StatusEffects {
+    pub fn add(&mut self, status: Status) {
+        if status.turns == 0 {
+            return;
+        }
+
+        let refreshed = self.0.iter_mut().find(|other| {
+            status.kind.stacking() == StatusStacking::Refresh
+                && other.caster == status.caster
+                && other.kind.is_same_kind(&status.kind)
+        });
+
+        match refreshed {
+            Some(other) => {
+                if status.turns >= other.turns {
+                    other.turns = status.turns;
+                    other.started = status.started;
+                }
+                other.kind = status.kind;
+            }
+            None => self.0.push(status),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Status> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.0.iter().any(|status| status.kind == StatusKind::Stun)
+    }
+
+    /// Consume the damages absorbed by the shield, the shields expiring first are consumed first
+    pub fn absorb(&mut self, mut amount: u32) {
+        self.0.sort_by_key(|status| status.turns);
+        for status in self.0.iter_mut() {
+            if let StatusKind::Shield {
+                amount: ref mut shield,
+            } = status.kind
+            {
+                let absorbed = (*shield).min(amount);
+                *shield -= absorbed;
+                amount -= absorbed;
+            }
+        }
+        self.0
+            .retain(|status| status.kind != StatusKind::Shield { amount: 0 });
+    }
+
+    /// Start a turn of the warrior, from now on all its statuses count down
+    pub fn start_turn(&mut self) {
+        for status in self.0.iter_mut() {
+            status.started = true;
+        }
+    }
+
+    /// Count down a turn of the started statuses, removing and returning the expired ones
+    pub fn tick(&mut self) -> Vec<Status> {
+        for status in self.0.iter_mut().filter(|status| status.started) {
+            status.turns -= 1;
+        }
+
+        let (expired, active) = self.0.drain(..).partition(|status| status.turns == 0);
+        self.0 = active;
+
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shield(turns: u32) -> Status {
+        Status::new(Entity::from_raw(0), StatusKind::Shield { amount: 100 }, turns)
+    }
+
+    #[test]
+    fn statuses_count_down_from_the_next_turn() {
+        let mut status_effects = StatusEffects::default();
+
+        // Given during the turn of the warrior, which ends right after
+        status_effects.add(shield(1));
+        assert!(status_effects.tick().is_empty());
+
+        status_effects.start_turn();
+        assert_eq!(status_effects.tick().len(), 1);
+        assert!(status_effects.is_empty());
+    }
+
+    #[test]
+    fn refreshed_statuses_restart_with_the_longest_duration() {
+        let mut status_effects = StatusEffects::default();
+        let stun = |turns| Status::new(Entity::from_raw(0), StatusKind::Stun, turns);
+
+        status_effects.add(stun(2));
+        status_effects.start_turn();
+        status_effects.add(stun(2));
+        assert!(status_effects.tick().is_empty());
+
+        let status = status_effects.iter().next().unwrap();
+        assert_eq!(status.turns, 2);
+        assert!(!status.started);
+    }
+}
@@ -3,12 +3,16 @@ use bevy::prelude::*;
 
 mod events;
 mod mouse;
+mod navgrid;
+mod occupancy;
 mod position;
 mod query;
+mod reachable;
 mod tiledmap;
 
 use events::trigger_map_mouse_events;
 use mouse::update_map_mouse_position;
+use tiledmap::despawn_tiledmap;
 use tiledmap::spawn_tiledmap;
 use tiledmap::TiledmapLoader;
 
@@ -16,13 +20,18 @@ pub use events::TileLeftClickedEvent;
 pub use events::TileRightClickedEvent;
 pub use mouse::MouseMapPosition;
 pub use mouse::PreviousMouseMapPosition;
+pub use navgrid::NavGrid;
+pub use occupancy::Occupancy;
 pub use position::*;
 pub use query::MapQuery;
+pub use reachable::ReachablePositions;
 pub use tiledmap::Layer;
 pub use tiledmap::Map;
 pub use tiledmap::MapsAssets;
 pub use tiledmap::Tile;
 pub use tiledmap::Tiledmap;
+pub use tiledmap::SPAWN_TEAM_A_LAYER;
+pub use tiledmap::SPAWN_TEAM_B_LAYER;
 
 pub struct TiledmapPlugin;
 
@@ -34,16 +43,21 @@ impl Plugin for TiledmapPlugin {
             .register_type::<Tile>()
             .init_resource::<MouseMapPosition>()
             .init_resource::<PreviousMouseMapPosition>()
+            .init_resource::<NavGrid>()
             .add_event::<TileLeftClickedEvent>()
             .add_event::<TileRightClickedEvent>()
             .add_asset::<Tiledmap>()
             .add_asset_loader(TiledmapLoader)
-            .add_system_set(SystemSet::on_enter(GameState::Arena).with_system(spawn_tiledmap))
+            .add_system_set(SystemSet::on_enter(GameState::Placement).with_system(spawn_tiledmap))
+            .add_system_set(
+                SystemSet::on_update(GameState::Placement).with_system(update_map_mouse_position),
+            )
             .add_system_set(
                 SystemSet::on_update(GameState::Arena)
                     .with_system(update_map_mouse_position)
                     .with_system(trigger_map_mouse_events),
-            );
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Results).with_system(despawn_tiledmap));
     }
 }
 
@@ -0,0 +1,312 @@
+use super::{MapPosition, Occupancy, ReachablePositions};
+use bevy::prelude::Entity;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// A flat copy of the current map obstacles and occupants, to answer movement
+/// and sight queries without walking the map layers and tiles
+#[derive(Debug, Default, Clone)]
+pub struct NavGrid {
+    width: u32,
+    height: u32,
+
+    /// Cells which can't be walked on nor seen through
+    blocked: Vec<bool>,
+
+    /// Movement points needed to walk on each cell
+    costs: Vec<u32>,
+
+    /// Entity standing on each cell
+    occupants: Vec<Option<Entity>>,
+
+    /// Cell of each occupant, to move it without scanning the grid
+    occupant_positions: HashMap<Entity, MapPosition>,
+}
+
+impl NavGrid {
+    /// Create a grid where all the cells can be walked on
+    pub fn new(width: u32, height: u32) -> Self {
+        let size = (width * height) as usize;
+
+        Self {
+            width,
+            height,
+            blocked: vec![false; size],
+            costs: vec![1; size],
+            occupants: vec![None; size],
+            occupant_positions: HashMap::new(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn index(&self, position: &MapPosition) -> Option<usize> {
+        if position.is_in_map_bounds(self.width, self.height) {
+            Some((position.y * self.width + position.x) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Is a position an obstacle or out of the map ?
+    pub fn is_blocked(&self, position: &MapPosition) -> bool {
+        self.index(position)
+            .map(|index| self.blocked[index])
+            .unwrap_or(true)
+    }
+
+    pub fn set_blocked(&mut self, position: &MapPosition, blocked: bool) {
+        if let Some(index) = self.index(position) {
+            self.blocked[index] = blocked;
+        }
+    }
+
+    fn is_occupied(&self, position: &MapPosition) -> bool {
+        self.index(position)
+            .map(|index| self.occupants[index].is_some())
+            .unwrap_or(false)
+    }
+
+    /// Move an occupant to its new position, leaving its previous one
+    pub fn set_occupant(&mut self, entity: Entity, position: MapPosition) {
+        self.remove_occupant(entity);
+
+        if let Some(index) = self.index(&position) {
+            self.occupants[index] = Some(entity);
+        }
+        self.occupant_positions.insert(entity, position);
+    }
+
+    pub fn remove_occupant(&mut self, entity: Entity) {
+        if let Some(position) = self.occupant_positions.remove(&entity) {
+            if let Some(index) = self.index(&position) {
+                // Another occupant may have been moved on the same position
+                if self.occupants[index] == Some(entity) {
+                    self.occupants[index] = None;
+                }
+            }
+        }
+    }
+
+    /// Get the neightbours of a position which can be crossed, with the cost to move on them
+    /// from the position, including the cost to leave the zone of control of an enemy
+    fn walkable_neightbours(
+        &self,
+        position: &MapPosition,
+        occupancy: &Occupancy,
+    ) -> Vec<(MapPosition, u32)> {
+        #[rustfmt::skip]
+        let neightbours = [
+            MapPosition::new(position.x, position.y.wrapping_add(1)), // Up
+            MapPosition::new(position.x.wrapping_sub(1), position.y), // Left
+            MapPosition::new(position.x.wrapping_add(1), position.y),  // Right
+            MapPosition::new(position.x, position.y.wrapping_sub(1)), // Down
+        ];
+
+        let leave_cost = occupancy.leave_cost(position);
+
+        neightbours
+            .iter()
+            .filter(|&position| !self.is_blocked(position))
+            .filter(|&position| !occupancy.is_blocking(position))
+            .filter_map(|&position| {
+                self.index(&position)
+                    .map(|index| (position, self.costs[index] + leave_cost))
+            })
+            .collect()
+    }
+
+    /// Get all the positions reachable from the start position within the movement budget,
+    /// avoiding obstacles and occupied positions, in a single bounded search
+    pub fn reachable_positions(
+        &self,
+        start: &MapPosition,
+        budget: u32,
+        occupancy: &Occupancy,
+    ) -> ReachablePositions {
+        let mut reachable = ReachablePositions::new(*start);
+        let mut costs = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        costs.insert(*start, 0);
+        queue.push(Reverse((0, start.x, start.y)));
+
+        while let Some(Reverse((cost, x, y))) = queue.pop() {
+            let position = MapPosition::new(x, y);
+            if costs.get(&position).map_or(false, |&best| best < cost) {
+                continue;
+            }
+
+            for (next, step_cost) in self.walkable_neightbours(&position, occupancy) {
+                let next_cost = cost + step_cost;
+                if next_cost > budget || costs.get(&next).map_or(false, |&best| best <= next_cost) {
+                    continue;
+                }
+
+                costs.insert(next, next_cost);
+                reachable.insert(next, next_cost, position, !occupancy.is_occupied(&next));
+                queue.push(Reverse((next_cost, next.x, next.y)));
+            }
+        }
+
+        reachable
+    }
+
+    /// Return if a line of sight to the given position is free of obstacles and occupants.
+    /// Both ends of the line are ignored, they are the caster and target cells.
+    pub fn line_of_sight_check(&self, me: &MapPosition, target: &MapPosition) -> bool {
+        me.line_to(target)
+            .iter()
+            .filter(|&position| position.ne(me) && position.ne(target))
+            .all(|position| !self.is_blocked(position) && !self.is_occupied(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occupancy(blocking: Vec<MapPosition>, passable: Vec<MapPosition>) -> Occupancy {
+        Occupancy {
+            blocking,
+            passable,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reachable_positions_avoid_obstacles() {
+        let mut nav_grid = NavGrid::new(5, 5);
+        for y in 0..4 {
+            nav_grid.set_blocked(&MapPosition::new(2, y), true);
+        }
+
+        let reachable =
+            nav_grid.reachable_positions(&MapPosition::new(0, 0), 4, &Occupancy::default());
+
+        assert_eq!(reachable.cost(&MapPosition::new(1, 0)), Some(1));
+        assert_eq!(reachable.cost(&MapPosition::new(1, 3)), Some(4));
+        assert_eq!(reachable.cost(&MapPosition::new(2, 0)), None);
+        assert_eq!(reachable.cost(&MapPosition::new(3, 0)), None);
+    }
+
+    #[test]
+    fn reachable_positions_stop_at_the_budget() {
+        let nav_grid = NavGrid::new(10, 1);
+
+        let reachable =
+            nav_grid.reachable_positions(&MapPosition::new(0, 0), 3, &Occupancy::default());
+
+        assert_eq!(reachable.cost(&MapPosition::new(3, 0)), Some(3));
+        assert_eq!(reachable.cost(&MapPosition::new(4, 0)), None);
+        assert_eq!(reachable.iter().count(), 3);
+    }
+
+    #[test]
+    fn reachable_positions_never_cross_blocking_occupants() {
+        let nav_grid = NavGrid::new(5, 1);
+        let occupancy = occupancy(vec![MapPosition::new(1, 0)], vec![]);
+
+        let reachable = nav_grid.reachable_positions(&MapPosition::new(0, 0), 4, &occupancy);
+
+        assert_eq!(reachable.iter().count(), 0);
+    }
+
+    #[test]
+    fn reachable_positions_cross_passable_occupants_without_stopping() {
+        let nav_grid = NavGrid::new(5, 1);
+        let occupancy = occupancy(vec![], vec![MapPosition::new(1, 0)]);
+
+        let reachable = nav_grid.reachable_positions(&MapPosition::new(0, 0), 4, &occupancy);
+
+        assert_eq!(reachable.cost(&MapPosition::new(1, 0)), None);
+        assert_eq!(reachable.cost(&MapPosition::new(2, 0)), Some(2));
+        assert_eq!(
+            reachable.path_to(&MapPosition::new(2, 0)),
+            Some(vec![
+                MapPosition::new(0, 0),
+                MapPosition::new(1, 0),
+                MapPosition::new(2, 0)
+            ])
+        );
+    }
+
+    #[test]
+    fn reachable_positions_charge_the_zone_of_control() {
+        let nav_grid = NavGrid::new(5, 1);
+        let occupancy = Occupancy {
+            blocking: vec![MapPosition::new(0, 0)],
+            enemies: vec![MapPosition::new(0, 0)],
+            zone_of_control_cost: 1,
+            ..Default::default()
+        };
+
+        let reachable = nav_grid.reachable_positions(&MapPosition::new(1, 0), 4, &occupancy);
+
+        assert_eq!(reachable.cost(&MapPosition::new(2, 0)), Some(2));
+        assert_eq!(reachable.cost(&MapPosition::new(3, 0)), Some(3));
+    }
+
+    #[test]
+    fn path_to_walks_around_obstacles() {
+        let mut nav_grid = NavGrid::new(3, 3);
+        nav_grid.set_blocked(&MapPosition::new(1, 0), true);
+        let start = MapPosition::new(0, 0);
+        let target = MapPosition::new(2, 0);
+
+        let reachable = nav_grid.reachable_positions(&start, 6, &Occupancy::default());
+        let path = reachable.path_to(&target).unwrap();
+
+        assert_eq!(reachable.cost(&target), Some(4));
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&target));
+        assert!(!path.contains(&MapPosition::new(1, 0)));
+        assert!(path
+            .windows(2)
+            .all(|step| step[0].distance_to(&step[1]) == 1));
+        assert_eq!(reachable.path_to(&MapPosition::new(1, 0)), None);
+    }
+
+    #[test]
+    fn line_of_sight_ignores_both_ends() {
+        let mut nav_grid = NavGrid::new(5, 1);
+        nav_grid.set_occupant(Entity::from_raw(0), MapPosition::new(0, 0));
+        nav_grid.set_occupant(Entity::from_raw(1), MapPosition::new(4, 0));
+
+        assert!(nav_grid.line_of_sight_check(&MapPosition::new(0, 0), &MapPosition::new(4, 0)));
+    }
+
+    #[test]
+    fn line_of_sight_is_blocked_by_obstacles_and_occupants() {
+        let mut nav_grid = NavGrid::new(5, 3);
+        nav_grid.set_blocked(&MapPosition::new(2, 0), true);
+        nav_grid.set_occupant(Entity::from_raw(0), MapPosition::new(2, 2));
+
+        assert!(!nav_grid.line_of_sight_check(&MapPosition::new(0, 0), &MapPosition::new(4, 0)));
+        assert!(!nav_grid.line_of_sight_check(&MapPosition::new(0, 2), &MapPosition::new(4, 2)));
+        assert!(nav_grid.line_of_sight_check(&MapPosition::new(0, 1), &MapPosition::new(4, 1)));
+    }
+
+    #[test]
+    fn removing_an_occupant_keeps_the_one_moved_on_its_position() {
+        let mut nav_grid = NavGrid::new(3, 3);
+        let (first, second) = (Entity::from_raw(0), Entity::from_raw(1));
+        let position = MapPosition::new(1, 1);
+
+        nav_grid.set_occupant(first, position);
+        nav_grid.set_occupant(second, position);
+        nav_grid.remove_occupant(first);
+        assert!(nav_grid.is_occupied(&position));
+
+        nav_grid.set_occupant(second, MapPosition::new(2, 2));
+        assert!(!nav_grid.is_occupied(&position));
+        assert!(nav_grid.is_occupied(&MapPosition::new(2, 2)));
+    }
+}
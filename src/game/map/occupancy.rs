@@ -0,0 +1,42 @@
+use super::MapPosition;
+
+/// The positions occupied by warriors, as seen by a moving warrior.
+/// A moving warrior never stops on an occupied position.
+#[derive(Debug, Default, Clone)]
+pub struct Occupancy {
+    /// Positions which can't be crossed
+    pub blocking: Vec<MapPosition>,
+
+    /// Positions which can be crossed
+    pub passable: Vec<MapPosition>,
+
+    /// Positions of the enemies, leaving a position next to them costs extra movement points
+    pub enemies: Vec<MapPosition>,
+
+    /// Extra movement points to leave a position next to an enemy
+    pub zone_of_control_cost: u32,
+}
+
+impl Occupancy {
+    pub fn is_blocking(&self, position: &MapPosition) -> bool {
+        self.blocking.contains(position)
+    }
+
+    pub fn is_occupied(&self, position: &MapPosition) -> bool {
+        self.blocking.contains(position) || self.passable.contains(position)
+    }
+
+    /// Get the extra movement points to leave a position, when it is next to an enemy
+    pub fn leave_cost(&self, position: &MapPosition) -> u32 {
+        let is_controlled = self
+            .enemies
+            .iter()
+            .any(|enemy| enemy.distance_to(position) == 1);
+
+        if is_controlled {
+            self.zone_of_control_cost
+        } else {
+            0
+        }
+    }
+}
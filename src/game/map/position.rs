@@ -107,9 +107,9 @@ impl MapPosition {
         positions
     }
 
-    /// Get all positions in the reach of the given position, excluding the given position
+    /// Is the position inside a map of the given size ?
     pub fn is_in_map_bounds(self, map_width: u32, map_height: u32) -> bool {
-        self.x != 0 && self.y != 0 && self.x < map_width && self.y < map_height
+        self.x < map_width && self.y < map_height
     }
 
     /// Get the direction of a target from the current position, only straight, no diagonals.
@@ -127,6 +127,28 @@ impl MapPosition {
         }
     }
 
+    /// Get the direction to look at to face a target from the current position, along its main axis.
+    pub fn facing_to(&self, target: &MapPosition) -> Option<MapPositionDirection> {
+        let dx = target.x as i32 - self.x as i32;
+        let dy = target.y as i32 - self.y as i32;
+
+        if dx == 0 && dy == 0 {
+            None
+        } else if dx.abs() >= dy.abs() {
+            Some(if dx > 0 {
+                MapPositionDirection::SudEst
+            } else {
+                MapPositionDirection::NordWest
+            })
+        } else {
+            Some(if dy > 0 {
+                MapPositionDirection::SudWest
+            } else {
+                MapPositionDirection::NordEst
+            })
+        }
+    }
+
     /// Get a straight path torward a position from the current position, unchecked for obstacles.
     pub fn unchecked_path_torward(
         &self,
@@ -135,12 +157,7 @@ impl MapPosition {
     ) -> Vec<MapPosition> {
         let mut distance = distance;
         let mut path = Vec::new();
-        let (dx, dy) = match direction {
-            MapPositionDirection::NordWest => (-1, 0),
-            MapPositionDirection::NordEst => (0, -1),
-            MapPositionDirection::SudWest => (0, 1),
-            MapPositionDirection::SudEst => (1, 0),
-        };
+        let (dx, dy) = direction.step();
 
         let (mut x, mut y) = (self.x as i32, self.y as i32);
         while distance != 0 {
@@ -152,6 +169,31 @@ impl MapPosition {
 
         path
     }
+
+    /// Get the diagonal step (x, y) torward a target from the current position, only diagonals.
+    pub fn diagonal_step_to(&self, target: &MapPosition) -> Option<(i32, i32)> {
+        let dx = target.x as i32 - self.x as i32;
+        let dy = target.y as i32 - self.y as i32;
+
+        if dx != 0 && dx.abs() == dy.abs() {
+            Some((dx.signum(), dy.signum()))
+        } else {
+            None
+        }
+    }
+
+    /// Get a diagonal path following the (x, y) step from the current position, unchecked for obstacles.
+    pub fn unchecked_diagonal_path_torward(
+        &self,
+        (dx, dy): (i32, i32),
+        distance: u32,
+    ) -> Vec<MapPosition> {
+        let (x, y) = (self.x as i32, self.y as i32);
+
+        (1..=distance as i32)
+            .map(|step| MapPosition::new((x + dx * step) as u32, (y + dy * step) as u32))
+            .collect()
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -162,6 +204,18 @@ pub enum MapPositionDirection {
     SudEst,   // bottom-right
 }
 
+impl MapPositionDirection {
+    /// Get the (x, y) step of a move in this direction
+    pub fn step(self) -> (i32, i32) {
+        match self {
+            Self::NordWest => (-1, 0),
+            Self::NordEst => (0, -1),
+            Self::SudWest => (0, 1),
+            Self::SudEst => (1, 0),
+        }
+    }
+}
+
 impl From<MapPosition> for (u32, u32) {
     fn from(position: MapPosition) -> Self {
         (position.x, position.y)
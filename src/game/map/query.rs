@@ -3,6 +3,7 @@ use bevy::ecs::system::SystemParam;
 
 #[derive(SystemParam)]
 pub struct MapQuery<'w, 's> {
+    pub nav_grid: Res<'w, NavGrid>,
     pub map_queryset: QuerySet<
         'w,
         's,
@@ -87,30 +88,29 @@ impl<'w, 's> MapQuery<'w, 's> {
     //     None
     // }
 
-    /// Get the tile Entity at the given position for the given map_id and layer_id
-    pub fn get_tile_entity_at(
-        &mut self,
-        map_id: u32,
-        layer_id: u32,
-        position: &MapPosition,
-    ) -> Option<Entity> {
+    /// Get all the positions of a spawn layer, sorted by row then column
+    pub fn get_spawn_positions(&mut self, map_id: u32, spawn_layer_id: u32) -> Vec<MapPosition> {
+        let mut positions = Vec::new();
+
         for (_, map, layers) in self.map_queryset.q1().iter() {
             if map.id.ne(&map_id) {
                 continue;
             }
 
             for (layer_entity, layer, _) in self.layer_queryset.q1().iter() {
-                if layer.id.ne(&layer_id) {
+                if layer.id.ne(&spawn_layer_id) {
                     continue;
                 }
                 if !layers.contains(&layer_entity) {
                     continue;
                 }
 
-                return layer.tiles.get(&(position.x, position.y)).copied();
+                positions.extend(layer.tiles.keys().map(|&(x, y)| MapPosition::new(x, y)));
             }
         }
-        None
+
+        positions.sort_by_key(|position| (position.y, position.x));
+        positions
     }
 
     /// Get the tile Entity at the given position for the given map_id and layer_id
@@ -179,82 +179,37 @@ impl<'w, 's> MapQuery<'w, 's> {
         None
     }
 
-    /// Return is a line of sight to the given position is blocked by an obstacle or not
-    pub fn line_of_sight_check(
-        &mut self,
-        map_id: u32,
-        me: &MapPosition,
-        target: &MapPosition,
-        map_width: u32,
-        map_height: u32,
-    ) -> bool {
-        me.line_to(&target)
-            .iter()
-            .all(|position| !self.is_obstacle(map_id, position, map_width, map_height))
-    }
-
     /// Is a map position an obstacle ?
-    pub fn is_obstacle(
-        &mut self,
-        map_id: u32,
-        position: &MapPosition,
-        map_width: u32,
-        map_height: u32,
-    ) -> bool {
-        if !position.is_in_map_bounds(map_width, map_height) {
-            return true;
-        }
-
-        for (_, map, _) in self.map_queryset.q1().iter() {
-            if map.id.ne(&map_id) {
-                continue;
-            }
-
-            let obstacle_layer = map.obstacle_layer;
-            let tile_entity = self.get_tile_entity_at(map_id, obstacle_layer, position);
-
-            return tile_entity.is_some();
-        }
-        false
+    pub fn is_obstacle(&self, position: &MapPosition) -> bool {
+        self.nav_grid.is_blocked(position)
     }
 
-    /// Get the list of tile neightbours at the given position
-    pub fn non_obstacle_tile_neightbours(
-        &mut self,
-        map_id: u32,
-        position: &MapPosition,
-        map_width: u32,
-        map_height: u32,
-    ) -> Vec<(MapPosition, u32)> {
-        #[rustfmt::skip]
-        let neightbours = vec![
-            MapPosition::new(position.x, position.y.wrapping_add(1)), // Up
-            MapPosition::new(position.x.wrapping_sub(1), position.y), // Left
-            MapPosition::new(position.x.wrapping_add(1), position.y),  // Right
-            MapPosition::new(position.x, position.y.wrapping_sub(1)), // Down
-        ];
+    /// Return if a line of sight to the given position is free of obstacles and warriors
+    pub fn line_of_sight_check(&self, me: &MapPosition, target: &MapPosition) -> bool {
+        self.nav_grid.line_of_sight_check(me, target)
+    }
 
-        neightbours
-            .iter()
-            .filter(|&position| position.x < map_width && position.y < map_height)
-            .filter(|&position| !self.is_obstacle(map_id, position, map_width, map_height))
-            .map(|&position| (position, 1))
-            .collect()
+    /// Get all the positions reachable from the start position within the movement budget,
+    /// with their cost and the path to walk there
+    pub fn reachable_positions(
+        &self,
+        start: &MapPosition,
+        budget: u32,
+        occupancy: &Occupancy,
+    ) -> ReachablePositions {
+        self.nav_grid.reachable_positions(start, budget, occupancy)
     }
 
-    /// Compute optimal path between two positions, avoiding obstacles, returning the path and it's cost
+    /// Compute optimal path between two positions, avoiding obstacles and occupants,
+    /// returning the path and it's cost
     pub fn pathfinding(
-        &mut self,
-        map_id: u32,
+        &self,
         start: &MapPosition,
         end: &MapPosition,
-        map_width: u32,
-        map_height: u32,
+        occupancy: &Occupancy,
     ) -> Option<(Vec<MapPosition>, u32)> {
-        pathfinding::prelude::dijkstra(
-            start,
-            |position| self.non_obstacle_tile_neightbours(map_id, position, map_width, map_height),
-            |position| position.eq(end),
-        )
+        let reachable = self.reachable_positions(start, u32::MAX, occupancy);
+
+        reachable.path_to(end).zip(reachable.cost(end))
     }
 }
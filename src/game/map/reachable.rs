@@ -0,0 +1,73 @@
+use super::MapPosition;
+use std::collections::HashMap;
+
+struct ReachableStep {
+    cost: u32,
+    previous: MapPosition,
+    can_stop: bool,
+}
+
+/// The positions reachable from a start position within a movement budget,
+/// with their cost and the previous position of their cheapest path
+pub struct ReachablePositions {
+    start: MapPosition,
+    steps: HashMap<MapPosition, ReachableStep>,
+}
+
+impl ReachablePositions {
+    pub fn new(start: MapPosition) -> Self {
+        Self {
+            start,
+            steps: HashMap::new(),
+        }
+    }
+
+    /// Record a cheaper way to reach a position
+    pub fn insert(
+        &mut self,
+        position: MapPosition,
+        cost: u32,
+        previous: MapPosition,
+        can_stop: bool,
+    ) {
+        self.steps.insert(
+            position,
+            ReachableStep {
+                cost,
+                previous,
+                can_stop,
+            },
+        );
+    }
+
+    /// Get the cost to reach a position, if a warrior can stop on it
+    pub fn cost(&self, position: &MapPosition) -> Option<u32> {
+        self.steps
+            .get(position)
+            .filter(|step| step.can_stop)
+            .map(|step| step.cost)
+    }
+
+    /// Get all the positions a warrior can stop on, with their cost
+    pub fn iter(&self) -> impl Iterator<Item = (&MapPosition, u32)> {
+        self.steps
+            .iter()
+            .filter(|(_, step)| step.can_stop)
+            .map(|(position, step)| (position, step.cost))
+    }
+
+    /// Get the path from the start position (included) to a position a warrior can stop on
+    pub fn path_to(&self, position: &MapPosition) -> Option<Vec<MapPosition>> {
+        self.cost(position)?;
+
+        let mut path = vec![*position];
+        let mut current = *position;
+        while current.ne(&self.start) {
+            current = self.steps.get(&current)?.previous;
+            path.push(current);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
@@ -8,11 +8,19 @@ use bevy_asset_loader::AssetCollection;
 use std::{io::BufReader, path::Path};
 
 use super::MapPosition;
+use super::NavGrid;
+
+/// Names of the map layers in the tiled files
+pub const GROUND_LAYER: &str = "ground";
+pub const HIGHLIGHT_LAYER: &str = "highlight";
+pub const OBSTACLE_LAYER: &str = "obstacles";
+pub const SPAWN_TEAM_A_LAYER: &str = "spawn_team_a";
+pub const SPAWN_TEAM_B_LAYER: &str = "spawn_team_b";
 
 #[derive(AssetCollection)]
 pub struct MapsAssets {
     #[asset(key = "maps.simple")]
-    simple: Handle<Tiledmap>,
+    pub simple: Handle<Tiledmap>,
 }
 
 #[derive(TypeUuid)]
@@ -23,6 +31,37 @@ pub struct Tiledmap {
     pub tileset: Handle<Image>,
 }
 
+impl Tiledmap {
+    /// Get the index of the layer with the given name
+    pub fn layer_index(&self, name: &str) -> Option<u32> {
+        self.inner
+            .layers
+            .iter()
+            .position(|layer| layer.name == name)
+            .map(|index| index as u32)
+    }
+
+    /// Get the positions of the tiles set on the layer with the given name, sorted by row then column
+    pub fn layer_positions(&self, name: &str) -> Vec<MapPosition> {
+        let layer = self.inner.layers.iter().find(|layer| layer.name == name);
+
+        match layer.map(|layer| &layer.tiles) {
+            Some(tiled::LayerData::Finite(tiles_y)) => tiles_y
+                .iter()
+                .enumerate()
+                .flat_map(|(y, tiles_x)| {
+                    tiles_x
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, tile)| tile.gid != 0)
+                        .map(move |(x, _)| MapPosition::new(x as u32, y as u32))
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
 #[derive(Reflect, Component, Default)]
 #[reflect(Component)]
 pub struct Map {
@@ -131,7 +170,20 @@ pub fn spawn_tiledmap(
     let map_entity = commands.spawn().id();
 
     if let Some(tiledmap) = tiledmaps.get(maps_assets.simple.clone()) {
-        let mut layer_entities = HashMap::default();
+        let mut nav_grid = NavGrid::new(tiledmap.inner.width, tiledmap.inner.height);
+        let mut map = Map {
+            id: tiledmap.id,
+            width: tiledmap.inner.width,
+            height: tiledmap.inner.height,
+            tile_width: tiledmap.inner.tile_width,
+            tile_height: tiledmap.inner.tile_height,
+            layers: HashMap::default(),
+            ground_layer: tiledmap.layer_index(GROUND_LAYER).unwrap_or(0),
+            highlight_layer: tiledmap.layer_index(HIGHLIGHT_LAYER).unwrap_or(1),
+            obstacle_layer: tiledmap.layer_index(OBSTACLE_LAYER).unwrap_or(2),
+            spawn_team_a_layer: tiledmap.layer_index(SPAWN_TEAM_A_LAYER).unwrap_or(3),
+            spawn_team_b_layer: tiledmap.layer_index(SPAWN_TEAM_B_LAYER).unwrap_or(4),
+        };
 
         let tileset = tiledmap
             .inner
@@ -150,7 +202,7 @@ pub fn spawn_tiledmap(
             let mut tile_entities = HashMap::default();
             let layer_index = layer_index as u32;
             let layer_entity = commands.spawn().insert(Name::new("layer")).id();
-            layer_entities.insert(layer_index, layer_entity);
+            map.layers.insert(layer_index, layer_entity);
             commands.entity(map_entity).add_child(layer_entity);
 
             if let tiled::LayerData::Finite(tiles_y) = &layer.tiles {
@@ -160,6 +212,10 @@ pub fn spawn_tiledmap(
                             continue;
                         }
                         let (x, y) = (tile_x as u32, tile_y as u32);
+                        if layer_index == map.obstacle_layer {
+                            nav_grid.set_blocked(&MapPosition::new(x, y), true);
+                        }
+
                         let tile_entity = commands
                             .spawn()
                             .insert(Name::new(format!("tile ({:02},{:02})", x, y)))
@@ -217,20 +273,17 @@ pub fn spawn_tiledmap(
             .insert_bundle(MapBundle {
                 transform: Transform::from_xyz(0.0, 0.0, 0.0),
                 tiledmap: maps_assets.simple.clone(),
-                map: Map {
-                    id: tiledmap.id,
-                    width: tiledmap.inner.width,
-                    height: tiledmap.inner.height,
-                    tile_width: tiledmap.inner.tile_width,
-                    tile_height: tiledmap.inner.tile_height,
-                    layers: layer_entities,
-                    ground_layer: 0,
-                    highlight_layer: 1,
-                    obstacle_layer: 2,
-                    spawn_team_a_layer: 3,
-                    spawn_team_b_layer: 4,
-                },
+                map,
                 ..Default::default()
             });
+
+        commands.insert_resource(nav_grid);
+    }
+}
+
+/// Despawn the map with all its layers and tiles
+pub fn despawn_tiledmap(mut commands: Commands, map_query: Query<Entity, With<Map>>) {
+    for entity in map_query.iter() {
+        commands.entity(entity).despawn_recursive();
     }
 }
@@ -4,7 +4,7 @@ use bevy_inspector_egui::WorldInspectorPlugin;
 
 mod color;
 mod gameplay;
-mod map;
+pub mod map;
 mod ui;
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
@@ -18,9 +18,15 @@ pub enum GameState {
     /// Prepare your team by picking
     Picking,
 
+    /// Place your warriors on the spawn cells of your team
+    Placement,
+
     /// Fight !
     Arena,
 
+    /// Show the winning team and the match statistics
+    Results,
+
     /// Game is paused (suspend turn timer)
     Paused,
 }
@@ -32,8 +38,6 @@ impl Plugin for GamePlugin {
         AssetLoader::new(GameState::Loading)
             .with_asset_collection_file("dynamic.assets")
             .with_collection::<map::MapsAssets>()
-            .with_collection::<gameplay::WarriorAssets>()
-            .with_collection::<ui::ActionsAssets>()
             .with_collection::<gameplay::WarriorCollection>()
             .with_collection::<gameplay::AnimationCollection>()
             .with_collection::<gameplay::IconCollection>()
@@ -52,6 +56,9 @@ impl Plugin for GamePlugin {
             .add_system_set(
                 SystemSet::on_update(GameState::Picking).with_system(ui::show_warrior_selection_ui),
             )
+            .add_system_set(
+                SystemSet::on_update(GameState::Placement).with_system(ui::show_placement_ui),
+            )
             .add_system_set(
                 SystemSet::on_update(GameState::Arena)
                     .with_system(ui::show_turn_ui)
@@ -62,7 +69,11 @@ impl Plugin for GamePlugin {
                     .with_system(ui::show_action_bar_ui)
                     .with_system(ui::handle_action_bar_shortcuts)
                     .with_system(ui::show_battlelog_ui)
-                    .with_system(ui::show_warrior_ui),
+                    .with_system(ui::show_warrior_ui)
+                    .with_system(ui::show_floating_texts_ui),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Results).with_system(ui::show_results_ui),
             )
             .add_system_set(
                 SystemSet::on_update(GameState::Paused), // .with_system(ui::show_pause_menu)
@@ -2,7 +2,6 @@ use super::color;
 use super::gameplay::*;
 use super::GameState;
 use bevy::prelude::*;
-use bevy_asset_loader::AssetCollection;
 use bevy_egui::egui;
 use bevy_egui::egui::{Label, ProgressBar, RichText};
 use bevy_egui::EguiContext;
@@ -15,6 +14,10 @@ pub fn show_warrior_selection_ui(
     warrior_collection: Res<WarriorCollection>,
     icon_collection: Res<IconCollection>,
     portraits_collection: Res<PortraitCollection>,
+    mut roster: ResMut<TeamRoster>,
+    mut draft: Option<ResMut<Draft>>,
+    mut draft_timer: ResMut<DraftTimer>,
+    mut commands: Commands,
 ) {
     for (index, icon) in icon_collection.get_all().iter().enumerate() {
         egui_context.set_egui_texture(10 + index as u64, icon.clone());
@@ -54,10 +57,42 @@ pub fn show_warrior_selection_ui(
                         .corner_radius(5.0),
                 )
                 .show_inside(ui, |ui| {
-                    ui.centered_and_justified(|ui| {
+                    ui.vertical_centered(|ui| {
                         ui.add(egui::Label::new(
                             egui::RichText::new("Pick your fighters").heading(),
                         ));
+
+                        ui.horizontal(|ui| {
+                            let free_button = ui.selectable_label(draft.is_none(), "Free picking");
+                            if free_button.clicked() && draft.is_some() {
+                                commands.remove_resource::<Draft>();
+                                roster.clear();
+                            }
+
+                            let draft_button = ui.selectable_label(draft.is_some(), "Draft");
+                            if draft_button.clicked() && draft.is_none() {
+                                commands.insert_resource(Draft::default());
+                                draft_timer.0.reset();
+                                roster.clear();
+                            }
+                        });
+
+                        // Show which team has to ban or pick a warrior
+                        if let Some(step) = draft.as_deref().and_then(|draft| draft.current_step())
+                        {
+                            let text = match step.action {
+                                DraftAction::Ban => format!("{} bans a warrior", step.side),
+                                DraftAction::Pick => format!("{} picks a warrior", step.side),
+                            };
+
+                            ui.label(RichText::new(text).heading().color(step.side.color()));
+                            ui.visuals_mut().selection.bg_fill = step.side.color().into();
+                            ui.visuals_mut().extreme_bg_color = color::DEFAULT_BG.into();
+                            ui.add(
+                                egui::ProgressBar::new(draft_timer.0.percent_left())
+                                    .desired_width(300.0),
+                            );
+                        }
                     })
                 });
 
@@ -72,9 +107,42 @@ pub fn show_warrior_selection_ui(
                         .corner_radius(5.0),
                 )
                 .show_inside(ui, |ui| {
-                    ui.centered_and_justified(|ui| {
-                        if ui.button("Play").clicked() {
-                            game_state.set(GameState::Arena).unwrap();
+                    ui.horizontal(|ui| {
+                        for side in [TeamSide::A, TeamSide::B] {
+                            let is_confirmed = roster.is_confirmed(side);
+                            ui.label(
+                                RichText::new(format!(
+                                    "{} {}/{}",
+                                    side,
+                                    roster.get(side).len(),
+                                    ROSTER_SIZE
+                                ))
+                                .heading()
+                                .color(side.color()),
+                            );
+
+                            let confirm_text = if is_confirmed { "Edit" } else { "Confirm" };
+                            // Draft mode confirms both rosters once completed
+                            let confirm_button = ui.add_enabled(
+                                roster.is_complete(side) && draft.is_none(),
+                                egui::Button::new(confirm_text),
+                            );
+
+                            if confirm_button.clicked() {
+                                if is_confirmed {
+                                    roster.unconfirm(side);
+                                } else {
+                                    roster.confirm(side);
+                                }
+                            }
+
+                            ui.separator();
+                        }
+
+                        let play_button =
+                            ui.add_enabled(roster.is_ready(), egui::Button::new("Play"));
+                        if play_button.clicked() {
+                            game_state.set(GameState::Placement).unwrap();
                         }
                     })
                 });
@@ -90,20 +158,98 @@ pub fn show_warrior_selection_ui(
                 .show_inside(ui, |ui| {
                     ui.horizontal_top(|ui| {
                         for warrior_handle in warrior_collection.warriors.iter() {
+                            // Highlight the warrior with the color of the team which picked it
+                            let stroke = match roster.picked_by(warrior_handle).as_slice() {
+                                [] => egui::Stroke::none(),
+                                [side] => egui::Stroke::new(4.0, side.color()),
+                                _ => egui::Stroke::new(4.0, color::HIGHLIGHT_BORDER),
+                            };
+
                             ui.vertical(|ui| {
-                                egui::Frame::default().show(ui, |ui| {
+                                egui::Frame::default().stroke(stroke).show(ui, |ui| {
                                     if let Some(warrior) = warriors.get(warrior_handle) {
+                                        let is_banned = draft
+                                            .as_deref()
+                                            .map(|draft| draft.is_banned(warrior_handle))
+                                            .unwrap_or(false);
+
                                         if let Some(texture_id) = portraits_collection
                                             .get_index(warrior.portrait_key.as_str())
                                         {
-                                            ui.image(
-                                                egui::TextureId::User(100 + texture_id as u64),
-                                                (325., 370.),
+                                            // Grey out banned warriors
+                                            let tint = if is_banned {
+                                                egui::Color32::from_gray(60)
+                                            } else {
+                                                egui::Color32::WHITE
+                                            };
+
+                                            ui.add(
+                                                egui::Image::new(
+                                                    egui::TextureId::User(100 + texture_id as u64),
+                                                    (325., 370.),
+                                                )
+                                                .tint(tint),
                                             );
                                         }
 
                                         ui.label(RichText::new(warrior.name.as_str()).heading());
 
+                                        if let Some(draft) = draft.as_deref_mut() {
+                                            if is_banned {
+                                                ui.label(
+                                                    RichText::new("Banned")
+                                                        .strong()
+                                                        .color(color::HEALTH),
+                                                );
+                                            } else if let Some(step) = draft.current_step() {
+                                                let text = match step.action {
+                                                    DraftAction::Ban => "Ban",
+                                                    DraftAction::Pick => "Pick",
+                                                };
+                                                let choose_button = ui.add_enabled(
+                                                    draft.can_choose(&roster, warrior_handle),
+                                                    egui::Button::new(
+                                                        RichText::new(text)
+                                                            .color(egui::Color32::BLACK),
+                                                    )
+                                                    .fill(step.side.color()),
+                                                );
+
+                                                if choose_button.clicked()
+                                                    && draft.choose(&mut roster, warrior_handle)
+                                                {
+                                                    draft_timer.0.reset();
+                                                }
+                                            }
+                                        } else {
+                                            ui.horizontal(|ui| {
+                                                for side in [TeamSide::A, TeamSide::B] {
+                                                    let is_picked =
+                                                        roster.is_picked(side, warrior_handle);
+                                                    let is_enabled = !roster.is_confirmed(side)
+                                                        && (is_picked
+                                                            || roster
+                                                                .can_pick(side, warrior_handle));
+                                                    let pick_button = ui.add_enabled(
+                                                        is_enabled,
+                                                        egui::Button::new(
+                                                            RichText::new(side.to_string())
+                                                                .color(egui::Color32::BLACK),
+                                                        )
+                                                        .fill(if is_picked {
+                                                            side.color()
+                                                        } else {
+                                                            color::HIGHLIGHT_BORDER
+                                                        }),
+                                                    );
+
+                                                    if pick_button.clicked() {
+                                                        roster.toggle(side, warrior_handle);
+                                                    }
+                                                }
+                                            });
+                                        }
+
                                         for action in warrior.actions.iter() {
                                             ui.label(
                                                 RichText::new(action.name.as_str()).monospace(),
@@ -127,38 +273,148 @@ pub fn show_warrior_selection_ui(
         });
 }
 
-// TODO actions unmock
-#[derive(AssetCollection)]
-pub struct ActionsAssets {
-    #[asset(path = "actions/blind.png")]
-    blind: Handle<Image>,
-
-    #[asset(path = "actions/cripple.png")]
-    cripple: Handle<Image>,
-
-    #[asset(path = "actions/heal.png")]
-    heal: Handle<Image>,
-
-    #[asset(path = "actions/push.png")]
-    push: Handle<Image>,
-
-    #[asset(path = "actions/shield.png")]
-    shield: Handle<Image>,
+/// Show the placement instructions and the button starting the fight
+pub fn show_placement_ui(
+    mut egui_context: ResMut<EguiContext>,
+    mut game_state: ResMut<State<GameState>>,
+) {
+    egui::containers::Window::new("placement")
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -20.0])
+        .collapsible(false)
+        .resizable(false)
+        .title_bar(false)
+        .frame(
+            egui::containers::Frame::default()
+                .margin((10.0, 10.0))
+                .fill(color::DEFAULT_BG.into())
+                .stroke(egui::Stroke::none())
+                .corner_radius(5.0),
+        )
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label(
+                    RichText::new("Drag your warriors on the spawn cells of your team")
+                        .color(color::BG_TEXT),
+                );
+
+                let fight_text = RichText::new("Fight!")
+                    .strong()
+                    .heading()
+                    .color(egui::Color32::BLACK);
+                let fight_button = ui.add(
+                    egui::Button::new(fight_text)
+                        .fill(color::HEALTH)
+                        .stroke(egui::Stroke::new(2.0, color::HIGHLIGHT_BORDER)),
+                );
+
+                if fight_button.clicked() {
+                    game_state.set(GameState::Arena).unwrap();
+                }
+            });
+        });
+}
 
-    #[asset(path = "actions/shoot.png")]
-    shoot: Handle<Image>,
+/// Show the winning team, the statistics of each warrior and the buttons to play again
+pub fn show_results_ui(
+    mut egui_context: ResMut<EguiContext>,
+    mut game_state: ResMut<State<GameState>>,
+    result: Res<MatchResult>,
+    stats: Res<MatchStats>,
+    mut roster: ResMut<TeamRoster>,
+    draft: Option<ResMut<Draft>>,
+    mut draft_timer: ResMut<DraftTimer>,
+) {
+    egui::containers::Window::new("results")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .title_bar(false)
+        .frame(
+            egui::containers::Frame::default()
+                .margin((20.0, 20.0))
+                .fill(color::DEFAULT_BG.into())
+                .stroke(egui::Stroke::none())
+                .corner_radius(5.0),
+        )
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.vertical_centered(|ui| {
+                let (title, title_color) = match result.winner {
+                    Some(side) => (format!("{} wins!", side), side.color()),
+                    None => ("Draw!".to_string(), color::BG_TEXT),
+                };
+                ui.label(RichText::new(title).heading().strong().color(title_color));
+                ui.label(
+                    RichText::new(format!("after {} turns", result.turns)).color(color::BG_TEXT),
+                );
+                ui.label(
+                    RichText::new(format!("seed {}", result.seed))
+                        .small()
+                        .color(color::HIGHLIGHT_BORDER),
+                );
+                ui.add_space(10.0);
+
+                egui::Grid::new("results_grid")
+                    .spacing((20.0, 5.0))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["Warrior", "Damage dealt", "Damage taken", "Healed"] {
+                            ui.label(RichText::new(header).strong().color(color::BG_TEXT));
+                        }
+                        ui.end_row();
+
+                        for side in [TeamSide::A, TeamSide::B] {
+                            for (_, warrior) in
+                                stats.iter().filter(|(_, warrior)| warrior.side == side)
+                            {
+                                ui.label(RichText::new(warrior.name.as_str()).color(side.color()));
+                                ui.label(
+                                    RichText::new(warrior.damage_dealt.to_string()).monospace(),
+                                );
+                                ui.label(
+                                    RichText::new(warrior.damage_taken.to_string()).monospace(),
+                                );
+                                ui.label(RichText::new(warrior.healed.to_string()).monospace());
+                                ui.end_row();
+                            }
+                        }
+                    });
+                ui.add_space(10.0);
 
-    #[asset(path = "actions/slash.png")]
-    slash: Handle<Image>,
+                ui.horizontal(|ui| {
+                    if ui.button(RichText::new("Rematch").heading()).clicked() {
+                        game_state.set(GameState::Placement).unwrap();
+                    }
 
-    #[asset(path = "actions/teleport.png")]
-    teleport: Handle<Image>,
+                    if ui
+                        .button(RichText::new("Back to picking").heading())
+                        .clicked()
+                    {
+                        if let Some(mut draft) = draft {
+                            *draft = Draft::default();
+                            draft_timer.0.reset();
+                        }
+                        roster.clear();
+                        game_state.set(GameState::Picking).unwrap();
+                    }
+                });
+            });
+        });
 }
 
 /// Display all infos about the turn system in a dedicated window
 pub fn show_turn_ui(
     turn: Res<Turn>,
-    warrior_query: Query<(&Name, &Health, &ActionPoints, &MovementPoints), With<Warrior>>,
+    warrior_query: Query<
+        (
+            &Name,
+            &Attribute<Health>,
+            &Erosion,
+            &Attribute<Shield>,
+            &Attribute<Initiative>,
+            &StatusEffects,
+        ),
+        With<Warrior>,
+    >,
     mut egui_context: ResMut<EguiContext>,
     mut team_query: Query<&Team, With<Warrior>>,
 ) {
@@ -179,12 +435,12 @@ pub fn show_turn_ui(
             let mut index = 0;
 
             ui.set_max_size([200.0, 1200.0].into());
-            ui.visuals_mut().selection.bg_fill = color::HEALTH.into();
 
             while display_slots > 0 {
                 let offset = if index == 0 { turn.order_index } else { 0 };
                 for &entity in turn.order.iter().skip(offset).take(display_slots) {
-                    let (name, health, _, _) = warrior_query.get(entity).unwrap();
+                    let (name, health, erosion, shield, initiative, status_effects) =
+                        warrior_query.get(entity).unwrap();
                     let color = team_query.get(entity).unwrap().color();
                     let stroke = if index == 0 && display_slots == turn.order.len() {
                         egui::Stroke::new(2.0, color::HIGHLIGHT_BORDER)
@@ -198,12 +454,16 @@ pub fn show_turn_ui(
                         .stroke(stroke)
                         .fill(color::DEFAULT_BG.into())
                         .show(ui, |ui| {
-                            ui.label(egui::RichText::new(name.as_str()).color(color).strong());
-                            ui.add(
-                                ProgressBar::new(health.as_percentage()).text(
-                                    egui::RichText::new(health.as_text()).color(color::BG_TEXT),
-                                ),
-                            );
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(name.as_str()).color(color).strong());
+                                ui.label(
+                                    egui::RichText::new(format!("⚡ {}", initiative.value()))
+                                        .small()
+                                        .color(color::BG_TEXT),
+                                );
+                            });
+                            add_health_bar(ui, health, erosion, shield, None);
+                            add_status_icons(ui, status_effects);
                         });
 
                     display_slots -= 1;
@@ -225,7 +485,13 @@ pub fn show_turn_button_ui(
     ev_turn_ended: EventWriter<TurnEnd>,
     mut egui_context: ResMut<EguiContext>,
     mut team_query: Query<&Team, With<Warrior>>,
+    busy_query: BusyWarriorQuery,
 ) {
+    let entity = match turn.get_current_warrior_entity() {
+        Some(entity) => entity,
+        None => return,
+    };
+
     egui::containers::Window::new("next_turn_button")
         .anchor(egui::Align2::RIGHT_BOTTOM, [-20.0, -20.0])
         .collapsible(false)
@@ -249,9 +515,9 @@ pub fn show_turn_button_ui(
                         .heading()
                         .color(egui::Color32::BLACK);
 
-                    let entity = turn.get_current_warrior_entity().unwrap();
                     let color = team_query.get(entity).unwrap().color();
-                    let is_enabled = !turn.is_changed();
+                    // The turn ends once the warriors are done walking and acting
+                    let is_enabled = !turn.is_changed() && busy_query.is_empty();
                     let end_turn_button = ui.add_enabled(
                         is_enabled,
                         egui::Button::new(end_turn_text)
@@ -275,8 +541,13 @@ pub fn show_turn_button_ui(
 pub fn show_health_bar_ui(
     mut egui_context: ResMut<EguiContext>,
     turn: Res<Turn>,
-    warrior_query: Query<&Health, With<Warrior>>,
+    warrior_query: Query<(&Attribute<Health>, &Erosion, &Attribute<Shield>), With<Warrior>>,
 ) {
+    let entity = match turn.get_current_warrior_entity() {
+        Some(entity) => entity,
+        None => return,
+    };
+
     egui::containers::Window::new("health_bar")
         .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -120.0])
         .collapsible(false)
@@ -290,23 +561,78 @@ pub fn show_health_bar_ui(
                 .corner_radius(5.0),
         )
         .show(egui_context.ctx_mut(), |ui| {
-            let entity = turn.get_current_warrior_entity().unwrap();
-            let health = warrior_query.get(entity).unwrap();
-
-            ui.visuals_mut().selection.bg_fill = color::HEALTH.into();
-            ui.add(
-                ProgressBar::new(health.as_percentage())
-                    .text(egui::RichText::new(health.as_text()).color(color::BG_TEXT))
-                    .desired_width(500.0),
-            );
+            let (health, erosion, shield) = warrior_query.get(entity).unwrap();
+
+            add_health_bar(ui, health, erosion, shield, Some(500.0));
         });
 }
 
+/// Show a health bar, with the shield drawn on top of it and the eroded health at its end
+fn add_health_bar(
+    ui: &mut egui::Ui,
+    health: &Attribute<Health>,
+    erosion: &Erosion,
+    shield: &Attribute<Shield>,
+    desired_width: Option<f32>,
+) -> egui::Response {
+    let text = if shield.value() > 0 {
+        format!("{} (+{})", health.as_text(), shield.value())
+    } else {
+        health.as_text()
+    };
+
+    // The bar is relative to the max health before any erosion
+    let base_max = health.max() + erosion.0;
+    let percentage = if base_max > 0 {
+        health.value() as f32 / base_max as f32
+    } else {
+        0.0
+    };
+
+    let mut health_bar =
+        ProgressBar::new(percentage).text(egui::RichText::new(text).color(color::BG_TEXT));
+    if let Some(width) = desired_width {
+        health_bar = health_bar.desired_width(width);
+    }
+
+    ui.visuals_mut().selection.bg_fill = color::HEALTH.into();
+    let response = ui.add(health_bar);
+    let rect = response.rect;
+
+    if erosion.0 > 0 && base_max > 0 {
+        let ratio = erosion.0 as f32 / base_max as f32;
+        let erosion_rect = egui::Rect::from_min_max(
+            egui::pos2(rect.max.x - rect.width() * ratio, rect.min.y),
+            rect.max,
+        );
+        ui.painter()
+            .rect_filled(erosion_rect, rect.height() / 2.0, color::EROSION);
+    }
+
+    if shield.value() > 0 && base_max > 0 {
+        let ratio = (shield.value() as f32 / base_max as f32).min(1.0);
+        let shield_rect =
+            egui::Rect::from_min_size(rect.min, egui::vec2(rect.width() * ratio, rect.height()));
+        ui.painter().rect_filled(
+            shield_rect,
+            rect.height() / 2.0,
+            egui::Color32::from(color::SHIELD).linear_multiply(0.6),
+        );
+    }
+
+    response
+}
+
 pub fn show_action_points_ui(
     mut egui_context: ResMut<EguiContext>,
     turn: Res<Turn>,
-    warrior_query: Query<&ActionPoints, With<Warrior>>,
+    warrior_query: Query<&Attribute<ActionPoints>, With<Warrior>>,
 ) {
+    let entity = match turn.get_current_warrior_entity() {
+        Some(entity) => entity,
+        None => return,
+    };
+
     egui::containers::Window::new("action_points")
         .anchor(egui::Align2::CENTER_BOTTOM, [-280.0, -78.0])
         .collapsible(false)
@@ -320,9 +646,8 @@ pub fn show_action_points_ui(
                 .corner_radius(5.0),
         )
         .show(egui_context.ctx_mut(), |ui| {
-            let entity = turn.get_current_warrior_entity().unwrap();
             let action_points = warrior_query.get(entity).unwrap();
-            let text = RichText::new(format!("★ {}", action_points.0.value))
+            let text = RichText::new(format!("★ {}", action_points.value()))
                 .strong()
                 .heading()
                 .color(egui::Color32::BLACK);
@@ -334,8 +659,13 @@ pub fn show_action_points_ui(
 pub fn show_movement_points_ui(
     mut egui_context: ResMut<EguiContext>,
     turn: Res<Turn>,
-    warrior_query: Query<&MovementPoints, With<Warrior>>,
+    warrior_query: Query<&Attribute<MovementPoints>, With<Warrior>>,
 ) {
+    let entity = match turn.get_current_warrior_entity() {
+        Some(entity) => entity,
+        None => return,
+    };
+
     egui::containers::Window::new("movement_points")
         .anchor(egui::Align2::CENTER_BOTTOM, [280.0, -78.0])
         .collapsible(false)
@@ -349,9 +679,8 @@ pub fn show_movement_points_ui(
                 .corner_radius(5.0),
         )
         .show(egui_context.ctx_mut(), |ui| {
-            let entity = turn.get_current_warrior_entity().unwrap();
             let movement_points = warrior_query.get(entity).unwrap();
-            let text = RichText::new(format!("🏃 {}", movement_points.0.value))
+            let text = RichText::new(format!("🏃 {}", movement_points.value()))
                 .strong()
                 .heading()
                 .color(egui::Color32::BLACK);
@@ -363,19 +692,18 @@ pub fn show_movement_points_ui(
 pub fn show_action_bar_ui(
     mut egui_context: ResMut<EguiContext>,
     mut selected_action: ResMut<SelectedAction>,
-    images: Res<ActionsAssets>,
+    icon_collection: Res<IconCollection>,
     turn: Res<Turn>,
-    warrior_query: Query<&ActionPoints, With<Warrior>>,
+    warrior_query: Query<(&Attribute<ActionPoints>, &Actions), With<Warrior>>,
 ) {
-    // TODO actions unmock
-    egui_context.set_egui_texture(0, images.slash.clone());
-    egui_context.set_egui_texture(1, images.shoot.clone());
-    egui_context.set_egui_texture(2, images.cripple.clone());
-    egui_context.set_egui_texture(3, images.blind.clone());
-    egui_context.set_egui_texture(4, images.push.clone());
-    egui_context.set_egui_texture(5, images.teleport.clone());
-    egui_context.set_egui_texture(6, images.shield.clone());
-    egui_context.set_egui_texture(7, images.heal.clone());
+    let entity = match turn.get_current_warrior_entity() {
+        Some(entity) => entity,
+        None => return,
+    };
+
+    for (index, icon) in icon_collection.get_all().iter().enumerate() {
+        egui_context.set_egui_texture(10 + index as u64, icon.clone());
+    }
 
     egui::containers::Window::new("action_bar")
         .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -20.0])
@@ -393,38 +721,27 @@ pub fn show_action_bar_ui(
             egui::Grid::new("action_bar_grid")
                 .spacing((5.0, 5.0))
                 .show(ui, |ui| {
-                    let entity = turn.get_current_warrior_entity().unwrap();
-                    let action_points = warrior_query.get(entity).unwrap();
+                    let (action_points, actions) = warrior_query.get(entity).unwrap();
 
-                    let action_count = 8usize;
-                    // TODO actions unmock
-                    for index in 0..action_count {
+                    for (index, action) in actions.iter().enumerate() {
                         if index > 0 && index % 8 == 0 {
                             ui.end_row();
                         }
 
-                        // TODO actions unmock
-                        let (name, cost) = match index {
-                            0 => ("Slash", 3),
-                            1 => ("Shoot", 5),
-                            2 => ("Cripple", 3),
-                            3 => ("Blind", 3),
-                            4 => ("Push", 2),
-                            5 => ("Teleport", 5),
-                            6 => ("Shield", 3),
-                            _ => ("Heal", 4),
-                        };
-
                         let is_selected = selected_action
                             .0
                             .map(|selected| selected == index)
                             .unwrap_or(false);
 
-                        let enabled = action_points.0.value >= cost; // TODO replace by the real action cost
+                        let texture_id = icon_collection
+                            .get_index(action.icon_key.as_str())
+                            .unwrap_or_default();
+
+                        let enabled = action_points.can_drop(action.cost.0);
                         let button = ui.add_enabled(
                             enabled,
                             egui::ImageButton::new(
-                                egui::TextureId::User(index as u64),
+                                egui::TextureId::User(10 + texture_id as u64),
                                 (48.0, 48.0),
                             )
                             .selected(is_selected),
@@ -441,57 +758,20 @@ pub fn show_action_bar_ui(
                                 egui::Grid::new(format!("action_bar_grid_{}", index)).show(
                                     ui,
                                     |ui| {
-                                        ui.label(egui::RichText::new(name).heading());
                                         ui.label(
-                                            egui::RichText::new(format!("★ {}", cost))
+                                            egui::RichText::new(action.name.as_str()).heading(),
+                                        );
+                                        ui.label(
+                                            egui::RichText::new(format!("★ {}", action.cost.0))
                                                 .heading()
                                                 .color(color::ACTION_POINTS),
                                         );
                                         ui.end_row();
 
-                                        // TODO actions unmock
-                                        match index {
-                                            0 => ui.label(
-                                                egui::RichText::new("-170 health")
-                                                    .strong()
-                                                    .color(color::HEALTH),
-                                            ),
-                                            1 => ui.label(
-                                                egui::RichText::new("-90 health")
-                                                    .strong()
-                                                    .color(color::HEALTH),
-                                            ),
-                                            2 => ui.label(
-                                                egui::RichText::new("-2 mp")
-                                                    .strong()
-                                                    .color(color::MOVEMENT_POINTS),
-                                            ),
-                                            3 => ui.label(
-                                                egui::RichText::new("-2 ap")
-                                                    .strong()
-                                                    .color(color::ACTION_POINTS),
-                                            ),
-                                            4 => ui.label(
-                                                egui::RichText::new("push target 2 tiles away")
-                                                    .strong()
-                                                    .color(color::MOVEMENT_POINTS),
-                                            ),
-                                            5 => ui.label(
-                                                egui::RichText::new("teleport yourself to target")
-                                                    .strong()
-                                                    .color(color::MOVEMENT_POINTS),
-                                            ),
-                                            6 => ui.label(
-                                                egui::RichText::new("+20 armor for 2 turns")
-                                                    .strong()
-                                                    .color(color::ACTION_POINTS),
-                                            ),
-                                            _ => ui.label(
-                                                egui::RichText::new("+120 health")
-                                                    .strong()
-                                                    .color(color::HEALTH),
-                                            ),
-                                        };
+                                        for effect in action.effects.iter() {
+                                            ui.label(action_effect_text(effect));
+                                            ui.end_row();
+                                        }
                                     },
                                 )
                             });
@@ -500,7 +780,7 @@ pub fn show_action_bar_ui(
 
                     // Show keybindigs below
                     ui.end_row();
-                    for index in 0..action_count {
+                    for index in 0..actions.len() {
                         ui.with_layout(
                             egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                             |ui| {
@@ -516,61 +796,176 @@ pub fn show_action_bar_ui(
         });
 }
 
+/// Describe an action effect with a short colored text
+fn action_effect_text(effect: &ActionEffect) -> RichText {
+    let (text, color) = match effect {
+        ActionEffect::Nothing => (String::from("nothing"), color::BG_TEXT),
+        ActionEffect::Damage { amount, .. } => (format!("-{} health", amount), color::HEALTH),
+        ActionEffect::DamageOverTime {
+            amount, duration, ..
+        } => (
+            format!("-{} health for {} turns", amount, duration),
+            color::HEALTH,
+        ),
+        ActionEffect::HealOverTime { amount, duration } => (
+            format!("+{} health for {} turns", amount, duration),
+            color::HEALTH,
+        ),
+        ActionEffect::Heal { amount } => (format!("+{} health", amount), color::HEALTH),
+        ActionEffect::RestoreErosion { amount } => {
+            (format!("+{} max health", amount), color::HEALTH)
+        }
+        ActionEffect::Shield { amount, duration } => match duration {
+            Some(duration) => (
+                format!("+{} shield ({} turns)", amount, duration),
+                color::SHIELD,
+            ),
+            None => (format!("+{} shield", amount), color::SHIELD),
+        },
+        ActionEffect::RemoveActionPoints { amount } => {
+            (format!("-{} ap", amount), color::ACTION_POINTS)
+        }
+        ActionEffect::StealActionPoints { amount } => {
+            (format!("steal {} ap", amount), color::ACTION_POINTS)
+        }
+        ActionEffect::RemoveMovementPoints { amount } => {
+            (format!("-{} mp", amount), color::MOVEMENT_POINTS)
+        }
+        ActionEffect::StealMovementPoints { amount } => {
+            (format!("steal {} mp", amount), color::MOVEMENT_POINTS)
+        }
+        ActionEffect::ActionPointsDebuff { amount, duration } => (
+            format!("-{} ap for {} turns", amount, duration),
+            color::ACTION_POINTS,
+        ),
+        ActionEffect::MovementPointsDebuff { amount, duration } => (
+            format!("-{} mp for {} turns", amount, duration),
+            color::MOVEMENT_POINTS,
+        ),
+        ActionEffect::Stun { duration } => (format!("stun for {} turns", duration), color::BG_TEXT),
+        ActionEffect::TeleportSelf => (
+            String::from("teleport yourself to target"),
+            color::MOVEMENT_POINTS,
+        ),
+        ActionEffect::TeleportSwitch => (
+            String::from("switch places with target"),
+            color::MOVEMENT_POINTS,
+        ),
+        ActionEffect::PushLinear { distance } => (
+            format!("push target {} tiles away", distance),
+            color::MOVEMENT_POINTS,
+        ),
+        ActionEffect::PushDiagonal { distance } => (
+            format!("push target {} tiles away diagonally", distance),
+            color::MOVEMENT_POINTS,
+        ),
+    };
+
+    RichText::new(text).strong().color(color)
+}
+
+/// Show the statuses of a warrior as small icons, described on hover
+fn add_status_icons(ui: &mut egui::Ui, status_effects: &StatusEffects) {
+    if status_effects.is_empty() {
+        return;
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        for status in status_effects.iter() {
+            let (icon, description, color) = match status.kind {
+                StatusKind::DamageOverTime { amount, .. } => {
+                    ("☠", format!("-{} health per turn", amount), color::HEALTH)
+                }
+                StatusKind::HealOverTime { amount } => {
+                    ("✚", format!("+{} health per turn", amount), color::HEALTH)
+                }
+                StatusKind::ActionPointsDebuff { amount } => (
+                    "★",
+                    format!("-{} ap per turn", amount),
+                    color::ACTION_POINTS,
+                ),
+                StatusKind::MovementPointsDebuff { amount } => (
+                    "🏃",
+                    format!("-{} mp per turn", amount),
+                    color::MOVEMENT_POINTS,
+                ),
+                StatusKind::Shield { amount } => {
+                    ("🛡", format!("+{} shield", amount), color::SHIELD)
+                }
+                StatusKind::Stun => ("💫", String::from("stunned"), color::BG_TEXT),
+            };
+
+            ui.label(
+                RichText::new(format!("{}{}", icon, status.turns))
+                    .small()
+                    .strong()
+                    .color(color),
+            )
+            .on_hover_text(format!("{} ({} turns left)", description, status.turns));
+        }
+    });
+}
+
 pub fn handle_action_bar_shortcuts(
     mut selected_action: ResMut<SelectedAction>,
     keys: Res<Input<KeyCode>>,
     turn: Res<Turn>,
-    warrior_query: Query<&ActionPoints, With<Warrior>>,
+    warrior_query: Query<(&Attribute<ActionPoints>, &Actions), With<Warrior>>,
+    busy_query: BusyWarriorQuery,
 ) {
     if keys.just_pressed(KeyCode::Escape) {
         selected_action.0 = None;
     }
 
-    let entity = turn.get_current_warrior_entity().unwrap();
-    let action_points = warrior_query.get(entity).unwrap();
-    let is_disabled = action_points.0.value < 3; // TODO replace by the real action cost, for each action
-
-    if is_disabled {
+    // No action can be selected until the warriors are done walking and acting
+    if !busy_query.is_empty() {
         return;
     }
 
-    // TODO switch to ScanCode to be layout agnostic
-    // see: https://bevy-cheatbook.github.io/input/keyboard.html#layout-agnostic-key-bindings
-    if keys.just_pressed(KeyCode::Key1) {
-        selected_action.0 = Some(0);
-    }
+    let warrior = turn
+        .get_current_warrior_entity()
+        .and_then(|entity| warrior_query.get(entity).ok());
 
-    if keys.just_pressed(KeyCode::Key2) {
-        selected_action.0 = Some(1);
-    }
-
-    if keys.just_pressed(KeyCode::Key3) {
-        selected_action.0 = Some(2);
-    }
-
-    if keys.just_pressed(KeyCode::Key4) {
-        selected_action.0 = Some(3);
-    }
+    let (action_points, actions) = match warrior {
+        Some(warrior) => warrior,
+        None => return,
+    };
 
-    if keys.just_pressed(KeyCode::Key5) {
-        selected_action.0 = Some(4);
-    }
-
-    if keys.just_pressed(KeyCode::Key6) {
-        selected_action.0 = Some(5);
-    }
+    // TODO switch to ScanCode to be layout agnostic
+    // see: https://bevy-cheatbook.github.io/input/keyboard.html#layout-agnostic-key-bindings
+    let shortcuts = [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+    ];
+
+    for (index, key) in shortcuts.iter().enumerate() {
+        if !keys.just_pressed(*key) {
+            continue;
+        }
 
-    if keys.just_pressed(KeyCode::Key7) {
-        selected_action.0 = Some(6);
-    }
+        let can_afford = actions
+            .get(index)
+            .map(|action| action_points.can_drop(action.cost.0))
+            .unwrap_or(false);
 
-    if keys.just_pressed(KeyCode::Key8) {
-        selected_action.0 = Some(7);
+        if can_afford {
+            selected_action.0 = Some(index);
+        }
     }
 }
 
 /// Show battle logs window (scrollable)
-pub fn show_battlelog_ui(mut egui_context: ResMut<EguiContext>) {
+pub fn show_battlelog_ui(
+    mut egui_context: ResMut<EguiContext>,
+    mut battlelog: ResMut<BattleLog>,
+    stats: Res<MatchStats>,
+) {
     egui::containers::Area::new("battlelogs")
         .anchor(egui::Align2::LEFT_BOTTOM, [20.0, -20.0])
         .show(egui_context.ctx_mut(), |ui| {
@@ -603,38 +998,209 @@ pub fn show_battlelog_ui(mut egui_context: ResMut<EguiContext>) {
                                 });
                         });
 
+                    // Only show the events involving the selected warrior
+                    let selected_text = battlelog
+                        .filter
+                        .and_then(|entity| stats.get(entity))
+                        .map(|warrior| warrior.name.clone())
+                        .unwrap_or_else(|| "All warriors".to_string());
+                    egui::ComboBox::from_id_source("battlelog_filter")
+                        .selected_text(selected_text)
+                        .width(300.0)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut battlelog.filter, None, "All warriors");
+                            for (entity, warrior) in stats.iter() {
+                                ui.selectable_value(
+                                    &mut battlelog.filter,
+                                    Some(entity),
+                                    RichText::new(warrior.name.as_str())
+                                        .color(warrior.side.color()),
+                                );
+                            }
+                        });
+
                     egui::Resize::default()
                         .default_width(300.0)
                         .default_height(200.0)
                         .resizable(false)
                         .show(ui, |ui| {
-                            let text_style = egui::TextStyle::Body;
-                            let row_height = ui.fonts()[text_style].row_height();
-                            let num_rows = 1_000;
-                            egui::ScrollArea::vertical().stick_to_bottom().show_rows(
-                                ui,
-                                row_height * 2.0,
-                                num_rows,
-                                |ui, row_range| {
-
-                                    for _ in row_range {
-                                        let message = egui::RichText::new("system: Brunto attacked Glurf with a Rusty Sword for 32 damages.").strong().color(color::DEFAULT_BG);
-                                            ui.label(message);
+                            egui::ScrollArea::vertical()
+                                .stick_to_bottom()
+                                .show(ui, |ui| {
+                                    for entry in battlelog.iter() {
+                                        ui.horizontal_wrapped(|ui| {
+                                            ui.spacing_mut().item_spacing.x = 0.0;
+                                            for text in battlelog_entry_texts(entry, &stats) {
+                                                ui.label(text);
+                                            }
+                                        });
                                     }
-                                },
-                            );
+                                });
                         });
                 });
         });
 }
 
+/// Describe a battle log entry, with the warriors names in the color of their team
+fn battlelog_entry_texts(entry: &BattleLogEntry, stats: &MatchStats) -> Vec<RichText> {
+    let name = |entity: Entity| match stats.get(entity) {
+        Some(warrior) => RichText::new(warrior.name.as_str())
+            .strong()
+            .color(warrior.side.color()),
+        None => RichText::new("Unknown").strong().color(color::DEFAULT_BG),
+    };
+    let text = |text: String| RichText::new(text).color(color::DEFAULT_BG);
+    let position = |position: &MapPosition| format!("({}, {})", position.x, position.y);
+
+    let mut texts = vec![RichText::new(format!("[{}] ", entry.turn + 1)).monospace()];
+    texts.extend(match &entry.event {
+        CombatEvent::ActionUsed { caster, action, .. } => vec![
+            name(*caster),
+            text(" uses ".to_string()),
+            RichText::new(action.as_str())
+                .strong()
+                .color(color::DEFAULT_BG),
+        ],
+        CombatEvent::Damage {
+            caster,
+            target,
+            amount,
+            absorbed,
+            is_crit,
+        } => {
+            let amount_text = if *is_crit {
+                format!("{} critical", amount)
+            } else {
+                amount.to_string()
+            };
+            let mut texts = vec![
+                name(*caster),
+                text(" deals ".to_string()),
+                RichText::new(amount_text).strong().color(color::HEALTH),
+                text(" damages to ".to_string()),
+                name(*target),
+            ];
+            if *absorbed > 0 {
+                texts.push(
+                    RichText::new(format!(" ({} absorbed)", absorbed))
+                        .strong()
+                        .color(color::SHIELD),
+                );
+            }
+            texts
+        }
+        CombatEvent::Heal {
+            caster,
+            target,
+            amount,
+        } => vec![
+            name(*caster),
+            text(" heals ".to_string()),
+            name(*target),
+            text(" for ".to_string()),
+            RichText::new(amount.to_string())
+                .strong()
+                .color(color::HEALTH),
+        ],
+        CombatEvent::ErosionRestored {
+            caster,
+            target,
+            amount,
+        } => vec![
+            name(*caster),
+            text(" restores ".to_string()),
+            RichText::new(amount.to_string())
+                .strong()
+                .color(color::HEALTH),
+            text(" max health of ".to_string()),
+            name(*target),
+        ],
+        CombatEvent::ShieldGained {
+            caster,
+            target,
+            amount,
+        } => vec![
+            name(*target),
+            text(format!(" gains {} shield from ", amount)),
+            name(*caster),
+        ],
+        CombatEvent::ActionPointsLost {
+            caster,
+            target,
+            amount,
+            stolen,
+        } => vec![
+            name(*caster),
+            text(if *stolen { " steals " } else { " removes " }.to_string()),
+            RichText::new(format!("{} AP", amount))
+                .strong()
+                .color(color::ACTION_POINTS),
+            text(if *stolen { " from " } else { " to " }.to_string()),
+            name(*target),
+        ],
+        CombatEvent::MovementPointsLost {
+            caster,
+            target,
+            amount,
+            stolen,
+        } => vec![
+            name(*caster),
+            text(if *stolen { " steals " } else { " removes " }.to_string()),
+            RichText::new(format!("{} MP", amount))
+                .strong()
+                .color(color::MOVEMENT_POINTS),
+            text(if *stolen { " from " } else { " to " }.to_string()),
+            name(*target),
+        ],
+        CombatEvent::Push { caster, target, to } => vec![
+            name(*caster),
+            text(" pushes ".to_string()),
+            name(*target),
+            text(format!(" to {}", position(to))),
+        ],
+        CombatEvent::Teleport { warrior, to } => vec![
+            name(*warrior),
+            text(format!(" teleports to {}", position(to))),
+        ],
+        CombatEvent::Movement { warrior, to, cost } => vec![
+            name(*warrior),
+            text(format!(" moves to {} for ", position(to))),
+            RichText::new(format!("{} MP", cost))
+                .strong()
+                .color(color::MOVEMENT_POINTS),
+        ],
+        CombatEvent::Death { warrior } => vec![name(*warrior), text(" dies".to_string())],
+        CombatEvent::TurnStart { warrior } => {
+            vec![name(*warrior), text(" starts its turn".to_string())]
+        }
+        CombatEvent::TurnEnd { warrior } => {
+            vec![name(*warrior), text(" ends its turn".to_string())]
+        }
+    });
+
+    texts
+}
+
 /// Show a bubble on top of the head of warrior on hover
 pub fn show_warrior_ui(
     windows: Res<Windows>,
     mouse_position: Res<MouseMapPosition>,
     selected_action: Res<SelectedAction>,
+    turn: Res<Turn>,
     map_query: Query<&Map>,
-    warrior_query: Query<(Entity, &Name, &Health, &MapPosition), With<Warrior>>,
+    warrior_query: Query<
+        (
+            Entity,
+            &Name,
+            &Attribute<Health>,
+            &Erosion,
+            &Attribute<Shield>,
+            &StatusEffects,
+            &MapPosition,
+        ),
+        With<Warrior>,
+    >,
+    actions_query: Query<&Actions, With<Warrior>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     mut egui_context: ResMut<EguiContext>,
     mut team_query: Query<&Team, With<Warrior>>,
@@ -649,8 +1215,15 @@ pub fn show_warrior_ui(
     if let Some(mouse_position) = mouse_position.0 {
         let map = map_query.single();
         let (camera, camera_transform) = camera_query.single();
+        let action = selected_action.0.and_then(|index| {
+            turn.get_current_warrior_entity()
+                .and_then(|entity| actions_query.get(entity).ok())
+                .and_then(|actions| actions.get(index))
+        });
 
-        for (entity, name, health, position) in warrior_query.iter() {
+        for (entity, name, health, erosion, shield, status_effects, position) in
+            warrior_query.iter()
+        {
             if mouse_position.ne(position) {
                 continue;
             }
@@ -686,28 +1259,63 @@ pub fn show_warrior_ui(
                     )
                     .show(egui_context.ctx_mut(), |ui| {
                         ui.label(egui::RichText::new(name.as_str()).color(color).heading());
-                        ui.visuals_mut().selection.bg_fill = color::HEALTH.into();
-                        ui.add(
-                            ProgressBar::new(health.as_percentage())
-                                .text(egui::RichText::new(health.as_text()).color(color::BG_TEXT)),
-                        );
+                        add_health_bar(ui, health, erosion, shield, None);
+                        add_status_icons(ui, status_effects);
 
                         // Preview selected action consequences on the hovered warrior
-                        if selected_action.0.is_some() {
+                        if let Some(action) = action {
                             ui.separator();
                             ui.label(
-                                egui::RichText::new("Slash")
+                                egui::RichText::new(action.name.as_str())
                                     .color(color::ACTION_POINTS)
                                     .text_style(egui::TextStyle::Button),
                             );
-                            ui.label(
-                                egui::RichText::new("-15 health")
-                                    .color(color::HEALTH)
-                                    .strong(),
-                            );
+                            for effect in action.effects.iter() {
+                                ui.label(action_effect_text(effect));
+                            }
                         }
                     });
             }
         }
     }
 }
+
+/// Show the floating combat texts above the warriors, crits in a larger font
+pub fn show_floating_texts_ui(
+    windows: Res<Windows>,
+    text_query: Query<(&FloatingText, &Transform)>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut egui_context: ResMut<EguiContext>,
+) {
+    if text_query.is_empty() {
+        return;
+    }
+
+    let (camera, camera_transform) = camera_query.single();
+    let main_window = windows.get_primary().unwrap();
+    let painter = egui_context.ctx_mut().layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("floating_texts"),
+    ));
+
+    for (text, transform) in text_query.iter() {
+        if let Some(screen_position) =
+            camera.world_to_screen(windows.as_ref(), camera_transform, transform.translation)
+        {
+            let text_style = if text.is_crit {
+                egui::TextStyle::Heading
+            } else {
+                egui::TextStyle::Button
+            };
+
+            painter.text(
+                // egui coordinates system has not the same 0.0 as bevy (top left vs bottom left)
+                egui::pos2(screen_position.x, main_window.height() - screen_position.y),
+                egui::Align2::CENTER_BOTTOM,
+                text.text.as_str(),
+                text_style,
+                egui::Color32::from(text.color).linear_multiply(text.opacity()),
+            );
+        }
+    }
+}
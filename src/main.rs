@@ -1,7 +1,6 @@
 // use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
 use bevy::prelude::*;
-
-mod game;
+use broutus::game;
 
 fn main() {
     App::new()